@@ -0,0 +1,141 @@
+use aoc_core::utils;
+use aoc_core::answer::Answer;
+use aoc_core::error::Error;
+use aoc_core::hash::HashSet;
+use aoc_core::params::{Param, Params};
+use aoc_macros::aoc;
+
+
+pub const PARAMS: &[Param] = &[
+    Param { name: "target", default: "2020", description: "Sum the entries should add up to" },
+];
+
+
+fn solve(set: HashSet<i64>, sum: i64) -> i64 {
+    for num in &set {
+        let complement = sum - num;
+        
+        if set.contains(&complement) {
+            return num * complement;
+        }
+    };
+
+    -1
+}
+
+fn solve_three(set: HashSet<i64>, sum: i64) -> i64 {
+    for num in &set {
+        let complement = sum - num;
+        let mut new_set = set.clone();
+        new_set.remove(num);
+        let two = solve(new_set, complement);
+
+        if two > 0 {
+            return two * num
+        }
+    };
+
+    -1
+}
+
+#[aoc(day = 1, part = 1, params = PARAMS)]
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    let set: HashSet<i64> = utils::parse_lines(input)?.into_iter().collect();
+    Ok(solve(set, params.get("target")?).into())
+}
+
+#[aoc(day = 1, part = 2, params = PARAMS)]
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    let set: HashSet<i64> = utils::parse_lines(input)?.into_iter().collect();
+    Ok(solve_three(set, params.get("target")?).into())
+}
+
+
+/// Sorts the entries and closes in on the target from both ends, instead of
+/// hashing them.
+pub mod two_pointer {
+    use super::*;
+
+    fn solve(entries: &[i64], sum: i64) -> i64 {
+        let (mut low, mut high) = (0, entries.len());
+
+        while low + 1 < high {
+            match entries[low] + entries[high - 1] {
+                s if s < sum => low += 1,
+                s if s > sum => high -= 1,
+                _ => return entries[low] * entries[high - 1],
+            }
+        }
+
+        -1
+    }
+
+    fn solve_three(entries: &[i64], sum: i64) -> i64 {
+        for (i, num) in entries.iter().enumerate() {
+            let two = solve(&entries[i + 1..], sum - num);
+
+            if two > 0 {
+                return two * num
+            }
+        }
+
+        -1
+    }
+
+    fn sorted_entries(input: &str) -> Result<Vec<i64>, Error> {
+        let mut entries: Vec<i64> = utils::parse_lines(input)?;
+        entries.sort_unstable();
+        Ok(entries)
+    }
+
+    #[aoc(day = 1, part = 1, name = "two_pointer", params = PARAMS)]
+    pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+        Ok(solve(&sorted_entries(input)?, params.get("target")?).into())
+    }
+
+    #[aoc(day = 1, part = 2, name = "two_pointer", params = PARAMS)]
+    pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+        Ok(solve_three(&sorted_entries(input)?, params.get("target")?).into())
+    }
+
+
+    #[test]
+    fn test_two_pointer() {
+        let entries = vec![299, 366, 675, 979, 1456, 1721];
+
+        assert_eq!(solve(&entries, 2020), 514579);
+        assert_eq!(solve_three(&entries, 2020), 241861950);
+        assert_eq!(solve(&[1, 2, 100], 100), -1);
+    }
+}
+
+
+#[test]
+fn test_example_report() {
+    let numbers: HashSet<i64> = vec![1721, 979, 366, 299, 675, 1456].into_iter().collect();
+    assert_eq!(solve(numbers, 2020), 514579)
+}
+
+#[test]
+fn test_no_solution() {
+    let numbers: HashSet<i64> = vec![1, 2, 100].into_iter().collect();
+    assert_eq!(solve(numbers, 100), -1)
+}
+
+#[test]
+fn test_example_report_three() {
+    let numbers: HashSet<i64> = vec![1721, 979, 366, 299, 675, 1456].into_iter().collect();
+    assert_eq!(solve_three(numbers, 2020), 241861950)
+}
+
+#[test]
+fn test_example_snapshot() {
+    let input = "1721\n979\n366\n299\n675\n1456\n";
+    let params = Params::defaults(PARAMS);
+
+    insta::assert_debug_snapshot!("example_answers", (part_1(input, &params), part_2(input, &params)));
+    insta::assert_debug_snapshot!(
+        "example_answers_two_pointer",
+        (two_pointer::part_1(input, &params), two_pointer::part_2(input, &params))
+    );
+}
@@ -0,0 +1,133 @@
+use aoc_core::utils;
+use aoc_core::algo::graph::Graph;
+use aoc_core::answer::Answer;
+use aoc_core::error::Error;
+use aoc_core::observe::{self, Frame};
+use aoc_core::params::Params;
+use aoc_macros::aoc;
+
+
+/// Fails if a gap is too big for any adapter to bridge.
+fn get_jolt_differences(jolts: &[usize]) -> Result<Vec<usize>, Error> {
+    let mut counts = vec![0; 4];
+
+    for pair in jolts.windows(2) {
+        *counts.get_mut(pair[1] - pair[0]).ok_or(Error::NoSolution)? += 1
+    }
+
+    Ok(counts)
+}
+
+
+/// An edge from each adapter to every later one it can connect to, by position
+/// in the sorted list.
+fn adapter_graph(jolts: &[usize]) -> Graph<usize> {
+    let mut graph = Graph::new();
+
+    for (i, jolt) in jolts.iter().enumerate() {
+        graph.add_node(i);
+        for (j, next) in jolts.iter().enumerate().skip(i + 1).take(3) {
+            if next - jolt <= 3 {
+                graph.add_edge(i, j, 1);
+            }
+        }
+    }
+
+    graph
+}
+
+
+fn number_of_paths(jolts: &[usize]) -> Result<u64, Error> {
+    let last = jolts.len() - 1;
+    // The counts from each adapter only depend on the ones after it
+    let paths = adapter_graph(jolts)
+        .path_counts_with(0, last, |i, paths| observe::step(|| table_frame(jolts, paths, i)))
+        .ok_or(Error::NoSolution)?;
+
+    Ok(paths[0])
+}
+
+
+/// The DP table so far: the number of paths from each adapter to the device.
+fn table_frame(jolts: &[usize], m: &[u64], current: usize) -> Frame {
+    let lines = jolts
+        .iter()
+        .zip(m)
+        .enumerate()
+        .map(|(i, (jolt, paths))| match i < current {
+            true => format!("{:>4}", jolt),
+            false => format!("{:>4} {:>16}", jolt, paths),
+        })
+        .collect();
+
+    Frame { caption: format!("paths from {} jolts", jolts[current]), lines, highlight: Some(current) }
+}
+
+
+fn get_jolts(input: &str) -> Result<Vec<usize>, Error> {
+    let mut jolts: Vec<usize> = utils::parse_lines(input)?;
+    jolts.sort();
+    jolts.insert(0, 0);
+    jolts.push(*jolts.last().unwrap() + 3);
+
+    Ok(jolts)
+}
+
+#[aoc(day = 10, part = 1)]
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let diffs = get_jolt_differences(&get_jolts(input)?)?;
+    Ok((diffs[1] * diffs[3]).into())
+}
+
+#[aoc(day = 10, part = 2)]
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    Ok(number_of_paths(&get_jolts(input)?)?.into())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_get_jolt_differences() {
+        let jolts: Vec<usize> = vec![0, 1, 2, 5, 7, 10, 11, 14];
+        let diffs = get_jolt_differences(&jolts);
+
+        assert_eq!(diffs, Ok(vec![0, 3, 1, 3]));
+        assert_eq!(get_jolt_differences(&[0, 1, 5]), Err(Error::NoSolution));
+    }
+
+    #[test]
+    fn test_number_of_paths() {
+        let mut jolts: Vec<usize> = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+        jolts.sort();
+
+        assert_eq!(number_of_paths(&jolts), Ok(8));
+    }
+
+    #[test]
+    fn test_number_of_paths_frames() {
+        let recorder = Rc::new(RefCell::new(observe::TextRecorder::new(usize::MAX)));
+        let jolts: Vec<usize> = vec![0, 1, 4, 5, 6, 7, 10];
+
+        let paths = observe::with_observer(recorder.clone(), || number_of_paths(&jolts));
+        let text = recorder.borrow().text().to_string();
+
+        assert_eq!(paths, Ok(4));
+        assert_eq!(text.matches("--- ").count(), jolts.len());
+        assert!(text.ends_with("--- 7: paths from 0 jolts\n>    0                4\n     1                4\n     4                4\n     5                2\n     6                1\n     7                1\n    10                1\n"));
+    }
+
+    #[test]
+    fn test_example_snapshot() {
+        let input = "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n";
+        let jolts = get_jolts(input).unwrap();
+        let params = Params::default();
+
+        insta::assert_debug_snapshot!("example_jolt_differences", get_jolt_differences(&jolts).unwrap());
+        insta::assert_debug_snapshot!("example_answers", (part_1(input, &params), part_2(input, &params)));
+    }
+}
@@ -0,0 +1,87 @@
+use aoc_core::utils;
+use aoc_core::answer::Answer;
+use aoc_core::error::{Error, ParseError};
+use aoc_core::params::Params;
+use aoc_macros::{aoc, RegexParse};
+
+/// The two numbers in a policy, its letter and the password.
+#[derive(Debug, PartialEq, RegexParse)]
+#[regex(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)")]
+pub struct Password(usize, usize, char, String);
+
+fn get_valid(passwords: &[String]) -> Result<Vec<Password>, ParseError> {
+    let parsed = passwords.iter().map(|x| x.parse()).collect::<Result<Vec<Password>, _>>()?;
+    Ok(parsed.into_iter().filter(is_valid).collect())
+}
+
+fn is_valid(password: &Password) -> bool {
+    let matches = password.3.matches(password.2).count();
+    password.0 <= matches && matches <= password.1
+}
+
+fn get_valid_part_2(passwords: &[String]) -> Result<Vec<Password>, ParseError> {
+    let parsed = passwords.iter().map(|x| x.parse()).collect::<Result<Vec<Password>, _>>()?;
+    Ok(parsed.into_iter().filter(is_valid_part_2).collect())
+}
+
+fn is_valid_part_2(password: &Password) -> bool {
+    let chars: Vec<char> = password.3.chars().collect();
+    let at = |position: usize| position.checked_sub(1).and_then(|i| chars.get(i)) == Some(&password.2);
+    at(password.0) ^ at(password.1)
+}
+
+#[aoc(day = 2, part = 1)]
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let passwords: Vec<String> = utils::parse_lines(input)?;
+    Ok(get_valid(&passwords)?.len().into())
+}
+
+#[aoc(day = 2, part = 2)]
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let passwords: Vec<String> = utils::parse_lines(input)?;
+    Ok(get_valid_part_2(&passwords)?.len().into())
+}
+
+#[test]
+fn test_get_valid() {
+    let passwords: Vec<String> = vec![
+        "1-3 a: abcde".to_string(),
+        "1-3 b: cdefg".to_string(),
+        "2-9 c: ccccccccc".to_string(),
+    ];
+
+    assert_eq!(get_valid(&passwords).unwrap().len(), 2)
+}
+
+
+#[test]
+fn test_is_valid_part_2() {
+    let pass1 = Password(1, 3, 'a', "abcde".to_string());
+    let pass2 = Password(1, 3, 'b', "cdefg".to_string());
+
+    assert!(is_valid_part_2(&pass1));
+    assert!(!is_valid_part_2(&pass2));
+}
+
+#[test]
+fn test_parse_password() {
+    let parsed = "1-3 a: abcde".parse();
+    assert_eq!(parsed, Ok(Password(1, 3, 'a', "abcde".to_string())))
+}
+
+#[test]
+fn test_parse_password_invalid() {
+    assert!("".parse::<Password>().is_err());
+    assert!("1-3 a:".parse::<Password>().is_err());
+    assert_eq!(
+        "99999999999999999999-3 a: abc".parse::<Password>(),
+        Err(ParseError::new("invalid Password field 0", "99999999999999999999-3 a: abc"))
+    );
+}
+
+#[test]
+fn test_is_valid_part_2_out_of_range() {
+    let pass = Password(0, 9, 'a', "abc".to_string());
+
+    assert!(!is_valid_part_2(&pass));
+}
\ No newline at end of file
@@ -0,0 +1,148 @@
+use aoc_core::utils;
+use aoc_core::answer::Answer;
+use aoc_core::error::{Error, ParseError};
+use aoc_core::geom::Point2;
+use aoc_core::observe::{self, Frame};
+use aoc_core::params::{Param, Params};
+use std::iter;
+use std::str::FromStr;
+use aoc_macros::aoc;
+type Grid = Vec<Vec<char>>;
+
+pub const PARAMS: &[Param] = &[
+    Param { name: "slope", default: "3,1", description: "Right,down step for part 1" },
+    Param { name: "slopes", default: "1,1 3,1 5,1 7,1 1,2", description: "Right,down steps to multiply for part 2" },
+];
+
+/// A `right,down` step, as written in the parameters.
+struct Slope(usize, usize);
+
+impl FromStr for Slope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (right, down) = s.split_once(',').ok_or(())?;
+        let slope = Slope(right.parse().map_err(|_| ())?, down.parse().map_err(|_| ())?);
+
+        // A zero step down never leaves the first row
+        if slope.1 == 0 { Err(()) } else { Ok(slope) }
+    }
+}
+
+fn count_trees(grid: &Grid, right: usize, down: usize) -> usize {
+    get_path(grid, right, down)
+        .iter()
+        .filter(|&c| c == &'#')
+        .count()
+}
+
+fn trees_multiplied(grid: &Grid, slopes: &[(usize, usize)]) -> i64 {
+    slopes.iter()
+        .map(|(right, down)| count_trees(grid, *right, *down) as i64)
+        .product()
+}
+
+fn get_path(grid: &Grid, right: usize, down: usize) -> Vec<char> {
+    let step = Point2::new(right as i64, down as i64);
+    let rows = grid.len() as i64;
+    let cols = grid[0].len() as i64;
+
+    // The pattern repeats to the right, so the toboggan wraps around
+    iter::successors(Some(Point2::ORIGIN), |&p| Some(p + step))
+        .take_while(|p| p.y() < rows)
+        .map(|p| {
+            let (x, y) = (p.x().rem_euclid(cols) as usize, p.y() as usize);
+            observe::step(|| path_frame(grid, x, y));
+            grid[y][x]
+        })
+        .collect()
+}
+
+/// The rows around the toboggan, marked `O` on open ground and `X` on a tree.
+fn path_frame(grid: &Grid, x: usize, y: usize) -> Frame {
+    let top = y.saturating_sub(4);
+    let lines = grid[top..grid.len().min(y + 5)]
+        .iter()
+        .enumerate()
+        .map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .map(|(col, &c)| match (top + row == y && col == x, c) {
+                    (true, '#') => 'X',
+                    (true, _) => 'O',
+                    (false, c) => c,
+                })
+                .collect()
+        })
+        .collect();
+
+    Frame { caption: format!("x={} y={}", x, y), lines, highlight: Some(y - top) }
+}
+
+fn get_grid(input: &str) -> Result<Grid, Error> {
+    let lines: Vec<String> = utils::parse_lines(input)?;
+    let grid: Grid = lines.iter().map(|l| l.chars().collect()).collect();
+
+    match grid.first().map(|row| row.len()) {
+        Some(width) if width > 0 && grid.iter().all(|row| row.len() == width) => Ok(grid),
+        _ => Err(ParseError::new("rows must be non-empty and equally wide", input).into()),
+    }
+}
+
+#[aoc(day = 3, part = 1, params = PARAMS)]
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    let grid = get_grid(input)?;
+    let Slope(right, down) = params.get("slope")?;
+
+    Ok(count_trees(&grid, right, down).into())
+}
+
+#[aoc(day = 3, part = 2, params = PARAMS)]
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    let grid = get_grid(input)?;
+    let part_2_slopes: Vec<(usize, usize)> = params
+        .get_list("slopes")?
+        .into_iter()
+        .map(|Slope(right, down)| (right, down))
+        .collect();
+
+    Ok(trees_multiplied(&grid, &part_2_slopes).into())
+}
+
+
+#[test]
+fn test_get_path() {
+    let grid: Vec<Vec<char>> = vec![
+        vec!['.', '.', '.', '.'],
+        vec!['.', '#', '.', '.'],
+        vec!['.', '.', '.', '.'],
+        vec!['.', '.', '.', '.'],
+        vec!['#', '.', '.', '.'],
+    ];
+    let expected = vec!['.', '#', '.', '.', '#'];
+
+    let path = get_path(&grid, 1, 1);
+
+    assert_eq!(path, expected);
+}
+
+#[test]
+fn test_trees_multiplied() {
+    let grid: Vec<Vec<char>> = vec![
+        vec!['.', '.', '.', '.'],
+        vec!['.', '#', '.', '.'],
+        vec!['.', '.', '.', '.'],
+        vec!['.', '.', '.', '.'],
+        vec!['#', '.', '.', '.'],
+    ];
+    let slopes: Vec<(usize, usize)> = vec![(1, 1), (1, 1), (1, 1)];
+
+    assert_eq!(trees_multiplied(&grid, &slopes), 8);
+}
+
+#[test]
+fn test_get_grid_invalid() {
+    assert!(get_grid("").is_err());
+    assert!(get_grid("..#\n.#").is_err());
+    assert_eq!(get_grid(".#\n#."), Ok(vec![vec!['.', '#'], vec!['#', '.']]));
+}
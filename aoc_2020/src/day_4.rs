@@ -1,10 +1,20 @@
-use std::fs;
-use regex::Regex;
 #[allow(unused_imports)] 
 use std::iter::FromIterator;
-use std::collections::HashMap;
-
-type Passport = HashMap<String, String>;
+use aoc_core::answer::Answer;
+use aoc_core::error::{Error, ParseError};
+use aoc_core::hash::HashMap;
+use aoc_core::params::Params;
+use aoc_core::regex::Regex;
+use aoc_macros::{aoc, RegexParse};
+
+pub type Passport = HashMap<String, String>;
+
+#[derive(RegexParse)]
+#[regex(r"^(?P<key>[a-z]{3}):(?P<value>\S+)$")]
+struct Field {
+    key: String,
+    value: String,
+}
 
 fn count_valid_passports(passports: &[Passport], strict: bool) -> usize {
     passports
@@ -33,9 +43,9 @@ fn is_valid(passport: &Passport, strict: bool) -> Option<bool> {
     let hgt_num: i64 = caps.get(1)?.as_str().parse().ok()?;
     let hgt_unit = caps.get(2)?.as_str();
     let hgt_valid = if hgt_unit == "cm" {
-        (hgt_num >= 150) && (hgt_num <= 193)
+        (150..=193).contains(&hgt_num)
     } else {
-        (hgt_num >= 59) && (hgt_num <= 76)
+        (59..=76).contains(&hgt_num)
     };
 
     let hcl_regex = Regex::new(r"^#[0-9|a-f]{6}$").unwrap();
@@ -43,9 +53,9 @@ fn is_valid(passport: &Passport, strict: bool) -> Option<bool> {
     let pid_regex = Regex::new(r"^[0-9]{9}$").unwrap();
 
     Some(
-        (byr >= 1920) && (byr <= 2002) &&
-        (iyr >= 2010) && (iyr <= 2020) &&
-        (eyr >= 2020) && (eyr <= 2030) &&
+        (1920..=2002).contains(&byr) &&
+        (2010..=2020).contains(&iyr) &&
+        (2020..=2030).contains(&eyr) &&
         hgt_valid &&
         hcl_regex.is_match(hcl) &&
         ecl_regex.is_match(ecl) &&
@@ -53,33 +63,26 @@ fn is_valid(passport: &Passport, strict: bool) -> Option<bool> {
     )
 }
 
-fn get_passports(lines: &[&str]) -> Vec<Passport> {
-    let regex = Regex::new(r"([a-z]{3}:\S+)").unwrap();
-
-    let key_sets: Vec<Passport> = lines.iter().map(|l| {
-        let captures = regex.captures_iter(l).collect::<Vec<_>>();
-        
-        captures
-            .iter()
-            .map(|c| {
-                let key_value = c.get(1).unwrap().as_str().to_string();
-                let split: Vec<_> = key_value.split(':').collect();
-
-                (split[0].to_string(), split[1].to_string())
-            }).collect()
-    }).collect();
-
-    key_sets
+pub fn get_passports(lines: &[&str]) -> Result<Vec<Passport>, ParseError> {
+    lines.iter().map(|l| {
+        l.split_whitespace()
+            .map(|field| field.parse().map(|Field { key, value }| (key, value)))
+            .collect()
+    }).collect()
 }
 
-pub fn main() {
-    let text = fs::read_to_string("data/day_4.txt").expect("File not found");
-    let lines: Vec<&str> = text.split("\n\n").collect();
-    let passports = get_passports(&lines);
+#[aoc(day = 4, part = 1)]
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let lines: Vec<&str> = input.split("\n\n").collect();
+    let passports = get_passports(&lines)?;
+    Ok(count_valid_passports(&passports, false).into())
+}
 
-    println!("======== Day 4 ========");
-    println!("Part 1 = {}", count_valid_passports(&passports, false));
-    println!("Part 2 = {}", count_valid_passports(&passports, true));
+#[aoc(day = 4, part = 2)]
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let lines: Vec<&str> = input.split("\n\n").collect();
+    let passports = get_passports(&lines)?;
+    Ok(count_valid_passports(&passports, true).into())
 }
 
 
@@ -103,5 +106,12 @@ fn test_get_key_sets() {
     ];
     let passorts = get_passports(&lines);
 
-    assert_eq!(passorts, expected);
+    assert_eq!(passorts, Ok(expected));
+}
+
+#[test]
+fn test_get_passports_invalid() {
+    assert!(get_passports(&["aaa:1 bbb"]).is_err());
+    assert!(get_passports(&["aaa:1\nb\u{e9}b:2"]).is_err());
+    assert_eq!(get_passports(&[""]), Ok(vec![HashMap::default()]));
 }
\ No newline at end of file
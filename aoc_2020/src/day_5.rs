@@ -0,0 +1,129 @@
+use aoc_core::utils;
+use std::str::FromStr;
+use aoc_core::answer::Answer;
+use aoc_core::error::{Error, ParseError};
+use aoc_core::params::Params;
+use aoc_macros::aoc;
+
+#[derive(Debug, PartialEq)]
+pub struct Seat {
+    row: usize,
+    column: usize
+}
+
+impl FromStr for Seat {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 10 || !s.is_ascii() {
+            return Err(ParseError::new("seat must be 10 characters", s));
+        }
+        let (row, column) = s.split_at(7);
+        let to_binary = |part: &str, zero: char, one: char| {
+            part.chars()
+                .map(|c| match c {
+                    c if c == zero => Ok(0),
+                    c if c == one => Ok(1),
+                    _ => Err(ParseError::new("invalid seat character", s)),
+                })
+                .try_fold(0, |acc, bit| Ok(2 * acc + bit?))
+        };
+
+        Ok(Self {
+            row: to_binary(row, 'F', 'B')?,
+            column: to_binary(column, 'L', 'R')?
+        })
+    }
+}
+
+impl Seat {
+    fn id(&self) -> usize {
+        8 * self.row + self.column
+    }
+}
+
+fn find_first_missing_seat(seats: &[usize]) -> Option<usize> {
+    let mut seats = seats.to_vec();
+    seats.sort();
+    
+    for pair in seats.windows(2) {
+        if pair[1] != pair[0] + 1 {
+            return Some(pair[0] + 1)
+        }
+    }
+
+    None
+}
+
+
+fn get_seat_ids(input: &str) -> Result<Vec<usize>, Error> {
+    let seats: Vec<Seat> = utils::parse_lines(input)?;
+    Ok(seats.iter().map(|s| s.id()).collect())
+}
+
+#[aoc(day = 5, part = 1)]
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let max_id = utils::process_results(utils::iter_lines(input), |seats| seats.map(|s: Seat| s.id()).max())?;
+    max_id.map(Answer::from).ok_or(Error::NoSolution)
+}
+
+#[aoc(day = 5, part = 2)]
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let seat_ids = get_seat_ids(input)?;
+    find_first_missing_seat(&seat_ids).map(Answer::from).ok_or(Error::NoSolution)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seat_from_string_and_id() {
+        let seat1: Seat = "BFFFBBFRRR".parse().unwrap();
+        let seat2: Seat = "FFFBBBFRRR".parse().unwrap();
+        let seat3: Seat = "BBFFBBFRLL".parse().unwrap();
+
+        assert_eq!(seat1, Seat{row: 70, column: 7});
+        assert_eq!(seat2, Seat{row: 14, column: 7});
+        assert_eq!(seat3, Seat{row: 102, column: 4});
+
+        assert_eq!(seat1.id(), 8 * 70 + 7);
+    }
+
+    #[test]
+    fn test_seat_from_invalid_string() {
+        assert!("".parse::<Seat>().is_err());
+        assert!("BFFFBBF".parse::<Seat>().is_err());
+        assert!("BFFFBBFRRX".parse::<Seat>().is_err());
+        assert!("RFFFBBFRRB".parse::<Seat>().is_err());
+        assert!("BFFFBB\u{e9}RR".parse::<Seat>().is_err());
+        assert_eq!(
+            part_1("BFFFBBFRRR\nBFFFBBFRRX\n", &Params::default()),
+            Err(ParseError::new("invalid seat character", "BFFFBBFRRX").into())
+        );
+    }
+
+    #[test]
+    fn test_find_missing_seat() {
+        let seats_missing = vec![4, 5, 6, 8, 9];
+        let seats_full = vec![100, 101, 102, 103, 104];
+        let seats_empty = vec![];
+        let seats_many_missing = vec![0, 1, 2, 3, 100];
+
+        assert_eq!(find_first_missing_seat(&seats_missing), Some(7));
+        assert_eq!(find_first_missing_seat(&seats_full), None);
+        assert_eq!(find_first_missing_seat(&seats_empty), None);
+        assert_eq!(find_first_missing_seat(&seats_many_missing), Some(4));
+    }
+
+    #[test]
+    fn test_example_snapshot() {
+        let input = "BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n";
+        let seats: Vec<Seat> = utils::parse_lines(input).unwrap();
+        let params = Params::default();
+
+        insta::assert_debug_snapshot!("example_seats", seats);
+        insta::assert_debug_snapshot!("example_answers", (part_1(input, &params), part_2(input, &params)));
+    }
+}
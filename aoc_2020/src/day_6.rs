@@ -0,0 +1,56 @@
+use aoc_core::answer::Answer;
+use aoc_core::bitset::BitSet;
+use aoc_core::error::Error;
+use aoc_core::params::Params;
+use aoc_macros::aoc;
+
+
+/// The questions one person answered yes to, by byte.
+type Answers = BitSet<4>;
+
+fn answers(person: &str) -> Answers {
+    person.bytes().map(usize::from).collect()
+}
+
+fn count_any_question(text: &str) -> Vec<usize> {
+    text
+        .split("\n\n")
+        .map(|group| group.split('\n').map(answers).fold(Answers::new(), |l, r| l.union(&r)).len())
+        .collect()
+}
+
+fn count_all_questions(text: &str) -> Vec<usize> {
+    text
+        .split("\n\n")
+        .map(|group| group.split('\n').map(answers).fold(Answers::full(), |l, r| l.intersection(&r)).len())
+        .collect()
+}
+
+#[aoc(day = 6, part = 1)]
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let num_any_question: usize = count_any_question(input.trim()).iter().sum();
+    Ok(num_any_question.into())
+}
+
+#[aoc(day = 6, part = 2)]
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let num_all_questions: usize = count_all_questions(input.trim()).iter().sum();
+    Ok(num_all_questions.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_any_question() {
+        let text = "ab\nbc\nq\n\nb\nd\nq\n";
+        assert_eq!(count_any_question(text), vec![4, 3]);
+    }
+
+    #[test]
+    fn test_count_all_questions() {
+        let text = "ab\nbc\nbqa\n\nb\ndb\nq\n";
+        assert_eq!(count_all_questions(text), vec![1, 0]);
+    }
+}
\ No newline at end of file
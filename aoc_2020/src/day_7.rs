@@ -0,0 +1,395 @@
+use aoc_core::utils;
+use std::str::FromStr;
+use aoc_core::algo::graph::Graph;
+use aoc_core::answer::Answer;
+use aoc_core::cancel;
+use aoc_core::error::{Error, ParseError};
+use aoc_core::hash::HashMap;
+use aoc_core::params::{Param, Params};
+use tracing::instrument;
+use aoc_macros::{aoc, aoc_generator, RegexParse};
+
+
+pub const PARAMS: &[Param] = &[
+    Param { name: "bag", default: "shiny gold", description: "The bag we're carrying" },
+];
+
+
+#[derive(Debug, PartialEq, RegexParse)]
+#[regex(r"^(\d+) (.+?) bag[s]*[.]*$")]
+pub struct Bags(usize, String);
+
+#[derive(Debug, PartialEq)]
+pub struct Rule {
+    bag: String,
+    can_contain: Option<Vec<Bags>>
+}
+
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (bag, contents) = s.split_once(" bags contain ").ok_or_else(|| ParseError::new("invalid rule", s))?;
+        let can_contain: Option<Vec<Bags>> = match contents {
+            "no other bags." => None,
+            s => Some(s.split(", ").map(|s| s.parse()).collect::<Result<_, _>>()?)
+        };
+
+        Ok(Self {
+            bag: bag.to_string(),
+            can_contain
+        })
+    }
+}
+
+
+fn get_rule_map(rules: &[Rule]) -> HashMap<String, &Vec<Bags>> {
+    rules
+        .iter()
+        .filter(|r| r.can_contain.is_some())
+        .map(|r| (r.bag.clone(), r.can_contain.as_ref().unwrap()))
+        .collect::<HashMap<_, _>>()
+}
+
+
+/// An edge from each bag to the bags it directly contains, weighted by how
+/// many of them it holds.
+fn bag_graph(rules: &[Rule]) -> Graph<&str> {
+    let mut graph = Graph::new();
+
+    for rule in rules {
+        graph.add_node(rule.bag.as_str());
+        for Bags(n, bag) in rule.can_contain.iter().flatten() {
+            graph.add_edge(rule.bag.as_str(), bag.as_str(), *n as u64);
+        }
+    }
+
+    graph
+}
+
+
+#[instrument(level = "trace", skip(graph), ret)]
+fn number_of_bags_containing(graph: &Graph<&str>, bag: &str) -> usize {
+    match graph.index(&bag) {
+        // Every bag that reaches this one, except itself
+        Some(index) => graph.reversed().dfs(index).len() - 1,
+        None => 0,
+    }
+}
+
+
+#[instrument(level = "trace", skip(graph), ret)]
+fn number_of_bags_in(graph: &Graph<&str>, bag: &str) -> Result<u64, Error> {
+    match graph.index(&bag) {
+        // A bag that ends up containing itself would hold infinitely many
+        Some(index) => graph.weighted_path_count(index).ok_or(Error::NoSolution),
+        None => Ok(0),
+    }
+}
+
+
+#[aoc_generator(day = 7)]
+pub fn parse_rules(input: &str) -> Result<Vec<Rule>, ParseError> {
+    utils::parse_lines(input)
+}
+
+#[aoc(day = 7, part = 1, params = PARAMS)]
+pub fn part_1(rules: &[Rule], params: &Params) -> Result<Answer, Error> {
+    let bag: String = params.get("bag")?;
+
+    Ok(number_of_bags_containing(&bag_graph(rules), &bag).into())
+}
+
+#[aoc(day = 7, part = 2, params = PARAMS)]
+pub fn part_2(rules: &[Rule], params: &Params) -> Result<Answer, Error> {
+    let bag: String = params.get("bag")?;
+
+    Ok(number_of_bags_in(&bag_graph(rules), &bag)?.into())
+}
+
+
+/// Caches the answer for every bag, so shared sub-bags are only visited once.
+pub mod memoized {
+    use super::*;
+
+    /// Whether `base_bag` holds `contain`, and whether that's settled. The
+    /// memo is `None` while a bag's contents are being searched, and a bag
+    /// that's reached again in that time doesn't hold it as far as this
+    /// path goes. A "no" found that way isn't settled, as the bag may still
+    /// hold it through the rest of its contents, so it isn't remembered.
+    #[instrument(level = "trace", skip(rule_map, memo), ret)]
+    fn can_contain<'a>(
+        base_bag: &'a str,
+        contain: &str,
+        rule_map: &'a HashMap<String, &Vec<Bags>>,
+        memo: &mut HashMap<&'a str, Option<bool>>,
+    ) -> Result<(bool, bool), Error> {
+        if base_bag == contain {
+            return Ok((true, true))
+        }
+        match memo.get(base_bag) {
+            Some(Some(known)) => return Ok((*known, true)),
+            Some(None) => return Ok((false, false)),
+            None => cancel::check()?,
+        }
+
+        memo.insert(base_bag, None);
+        let mut settled = true;
+        for b in rule_map.get(base_bag).into_iter().flat_map(|bags| bags.iter()) {
+            match can_contain(&b.1, contain, rule_map, memo)? {
+                (true, _) => {
+                    memo.insert(base_bag, Some(true));
+                    return Ok((true, true))
+                },
+                (false, done) => settled &= done,
+            }
+        }
+
+        if settled {
+            memo.insert(base_bag, Some(false));
+        } else {
+            memo.remove(base_bag);
+        }
+        Ok((false, settled))
+    }
+
+    /// The memo is `None` while a bag's contents are still being counted, so
+    /// a bag that ends up containing itself fails instead of recursing
+    /// forever.
+    #[instrument(level = "trace", skip(rule_map, memo), ret)]
+    fn number_of_bags_in<'a>(
+        rule_map: &'a HashMap<String, &Vec<Bags>>,
+        bag: &str,
+        memo: &mut HashMap<&'a str, Option<usize>>,
+    ) -> Result<usize, Error> {
+        match memo.get(bag) {
+            Some(Some(known)) => return Ok(*known),
+            Some(None) => return Err(Error::NoSolution),
+            None => cancel::check()?,
+        }
+
+        let (key, bags) = match rule_map.get_key_value(bag) {
+            Some(entry) => entry,
+            None => return Ok(0),
+        };
+        memo.insert(key, None);
+        let mut result = 0;
+        for b in bags.iter() {
+            // Each bag and everything inside it
+            let inside = number_of_bags_in(rule_map, &b.1, memo)?;
+            result = inside
+                .checked_add(1)
+                .and_then(|n| n.checked_mul(b.0))
+                .and_then(|n| n.checked_add(result))
+                .ok_or(Error::NoSolution)?;
+        }
+        memo.insert(key, Some(result));
+        Ok(result)
+    }
+
+    #[aoc(day = 7, part = 1, name = "memoized", params = PARAMS)]
+    pub fn part_1(rules: &[Rule], params: &Params) -> Result<Answer, Error> {
+        let rule_map = get_rule_map(rules);
+        let bag: String = params.get("bag")?;
+        let mut memo = HashMap::default();
+
+        let mut count = 0;
+        for b in rule_map.keys().filter(|b| **b != bag) {
+            // Nothing is being searched at the top, so every answer here is settled
+            if can_contain(b, &bag, &rule_map, &mut memo)?.0 {
+                count += 1;
+            }
+        }
+
+        Ok(count.into())
+    }
+
+    #[aoc(day = 7, part = 2, name = "memoized", params = PARAMS)]
+    pub fn part_2(rules: &[Rule], params: &Params) -> Result<Answer, Error> {
+        let rule_map = get_rule_map(rules);
+        let bag: String = params.get("bag")?;
+
+        Ok(number_of_bags_in(&rule_map, &bag, &mut HashMap::default())?.into())
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bags() {
+        let bags1: Bags = "1 dim aqua bag".parse().unwrap();
+        let bags2: Bags = "5 posh fuchsia bags".parse().unwrap();
+        let bags3: Bags = "2 blue bagish bags.".parse().unwrap();
+
+        assert_eq!(bags1, Bags(1, "dim aqua".to_string()));
+        assert_eq!(bags2, Bags(5, "posh fuchsia".to_string()));
+        assert_eq!(bags3, Bags(2, "blue bagish".to_string()));
+    }
+
+    #[test]
+    fn test_rule() {
+        let rule1: Rule = "mirrored maroon bags contain 3 drab coral bags.".parse().unwrap();
+        let rule2: Rule = "muted bronze bags contain 3 dull aqua bags, 4 striped turquoise bags.".parse().unwrap();
+        let rule3: Rule = "greenish bags contain no other bags.".parse().unwrap();
+
+        assert_eq!(
+            rule1,
+            Rule {
+                bag: "mirrored maroon".to_string(),
+                can_contain: Some(vec![Bags(3, "drab coral".to_string())])}
+        );
+
+        assert_eq!(
+            rule2,
+            Rule {
+                bag: "muted bronze".to_string(),
+                can_contain: Some(vec![Bags(3, "dull aqua".to_string()), Bags(4, "striped turquoise".to_string())])
+            }
+        );
+
+        assert_eq!(
+            rule3,
+            Rule {
+                bag: "greenish".to_string(),
+                can_contain: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_rule_invalid() {
+        assert!("".parse::<Rule>().is_err());
+        assert!("greenish bags contain".parse::<Rule>().is_err());
+        assert!("greenish bags contain 3 bags, no".parse::<Rule>().is_err());
+        assert!("a bags contain 99999999999999999999 b bags.".parse::<Rule>().is_err());
+    }
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+
+    #[test]
+    fn test_example_snapshot() {
+        let rules = parse_rules(EXAMPLE).unwrap();
+        let params = Params::defaults(PARAMS);
+
+        insta::assert_debug_snapshot!("example_answers", (part_1(&rules, &params), part_2(&rules, &params)));
+        insta::assert_debug_snapshot!("example_rules", rules);
+    }
+
+    #[test]
+    fn test_number_of_bags_containing() {
+        let rules: Vec<Rule> = vec![
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.".parse().unwrap(),
+            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.".parse().unwrap(),
+            "bright white bags contain 1 shiny gold bag.".parse().unwrap(),
+            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.".parse().unwrap(),
+            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.".parse().unwrap(),
+            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.".parse().unwrap(),
+            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.".parse().unwrap(),
+            "faded blue bags contain no other bags.".parse().unwrap(),
+            "dotted black bags contain no other bags.".parse().unwrap(),
+        ];
+        let graph = bag_graph(&rules);
+
+        let num = number_of_bags_containing(&graph, "shiny gold");
+
+        assert_eq!(num, 4);
+    }
+
+    #[test]
+    fn test_number_of_bags_in() {
+        let rules: Vec<Rule> = vec![
+            "shiny gold bags contain 2 dark red bags.".parse().unwrap(),
+            "dark red bags contain 2 dark orange bags.".parse().unwrap(),
+            "dark orange bags contain 2 dark yellow bags.".parse().unwrap(),
+            "dark yellow bags contain 2 dark green bags.".parse().unwrap(),
+            "dark green bags contain 2 dark blue bags.".parse().unwrap(),
+            "dark blue bags contain 2 dark violet bags.".parse().unwrap(),
+            "dark violet bags contain no other bags.".parse().unwrap(),
+        ];
+        let graph = bag_graph(&rules);
+
+        let num = number_of_bags_in(&graph, "shiny gold");
+
+        assert_eq!(num, Ok(126));
+    }
+
+    #[test]
+    fn test_number_of_bags_in_cycle() {
+        let rules: Vec<Rule> = vec![
+            "shiny gold bags contain 2 dark red bags.".parse().unwrap(),
+            "dark red bags contain 1 shiny gold bag.".parse().unwrap(),
+        ];
+
+        assert_eq!(number_of_bags_in(&bag_graph(&rules), "shiny gold"), Err(Error::NoSolution));
+        assert_eq!(number_of_bags_in(&bag_graph(&rules), "dull blue"), Ok(0));
+    }
+
+    #[test]
+    fn test_memoized() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+        let rules = parse_rules(input).unwrap();
+        let params = Params::defaults(PARAMS);
+
+        assert_eq!(memoized::part_1(&rules, &params), Ok(Answer::Integer(3)));
+        assert_eq!(memoized::part_2(&rules, &params), Ok(Answer::Integer(32)));
+    }
+
+    #[test]
+    fn test_memoized_cycle() {
+        let containing = parse_rules("shiny gold bags contain 2 dark red bags.\ndark red bags contain 1 shiny gold bag.").unwrap();
+        let elsewhere = parse_rules("dark red bags contain 2 dull blue bags.\ndull blue bags contain 1 dark red bag.").unwrap();
+        // Dull blue only reaches shiny gold through dark red, which may be
+        // searched first
+        let through = parse_rules(
+            "dark red bags contain 1 dull blue bag, 1 faded green bag.\n\
+             dull blue bags contain 1 dark red bag.\n\
+             faded green bags contain 1 shiny gold bag.",
+        )
+        .unwrap();
+        let params = Params::defaults(PARAMS);
+
+        assert_eq!(memoized::part_2(&containing, &params), Err(Error::NoSolution));
+        assert_eq!(memoized::part_1(&elsewhere, &params), Ok(Answer::Integer(0)));
+        for rules in [&containing, &elsewhere, &through] {
+            assert_eq!(memoized::part_1(rules, &params), part_1(rules, &params));
+        }
+        assert_eq!(memoized::part_1(&through, &params), Ok(Answer::Integer(3)));
+    }
+
+    #[test]
+    fn test_memoized_overflow() {
+        let rules = parse_rules(
+            "shiny gold bags contain 4000000000 dark red bags.\n\
+             dark red bags contain 4000000000 dull blue bags.\n\
+             dull blue bags contain 4000000000 faded green bags.\n\
+             faded green bags contain no other bags.",
+        )
+        .unwrap();
+        let params = Params::defaults(PARAMS);
+
+        assert_eq!(part_2(&rules, &params), Err(Error::NoSolution));
+        assert_eq!(memoized::part_2(&rules, &params), Err(Error::NoSolution));
+    }
+}
@@ -0,0 +1,286 @@
+use aoc_core::utils;
+use std::str::FromStr;
+use aoc_core::answer::Answer;
+use aoc_core::cancel;
+use aoc_core::error::{Error, ParseError};
+use aoc_core::observe::{self, Frame};
+use aoc_core::params::Params;
+use std::fmt;
+use tracing::{debug, debug_span, trace};
+use aoc_macros::aoc;
+
+#[derive(Debug)]
+pub enum Instruction {
+    Jump(isize),
+    Acc(isize),
+    Nop(isize),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (operation, argument) = s.split_once(' ').ok_or_else(|| ParseError::new("invalid instruction", s))?;
+        let arg = get_argument(argument)?;
+
+        match operation {
+            "nop" => Ok(Instruction::Nop(arg)),
+            "jmp" => Ok(Instruction::Jump(arg)),
+            "acc" => Ok(Instruction::Acc(arg)),
+            _ => Err(ParseError::new("unknown operation", s)),
+        }
+    }
+}
+
+impl Instruction {
+    fn swap(&self) -> Self {
+        match self {
+            Self::Jump(i) => Self::Nop(*i),
+            Self::Nop(i) => Self::Jump(*i),
+            Self::Acc(i) => Self::Acc(*i),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Jump(i) => write!(f, "jmp {:+}", i),
+            Self::Acc(i) => write!(f, "acc {:+}", i),
+            Self::Nop(i) => write!(f, "nop {:+}", i),
+        }
+    }
+}
+
+
+fn get_argument(s: &str) -> Result<isize, ParseError> {
+    // isize::from_str accepts an optional sign, but the puzzle always has one
+    match s.chars().next() {
+        Some('+') | Some('-') => s.parse().map_err(|_| ParseError::new("invalid argument", s)),
+        _ => Err(ParseError::new("argument must be signed", s)),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum EndStatus {
+    Normal,
+    InfiniteLoop,
+}
+
+#[derive(Debug, Clone)]
+struct ProgramState {
+    counter: usize,
+    visited: Vec<bool>,
+    accumulator: isize,
+}
+
+
+impl ProgramState {
+    fn new_with_capacity(cap: usize) -> Self {
+        Self {
+            counter: 0,
+            visited: vec![false; cap],
+            accumulator: 0,
+        }
+    }
+
+    /// Fails if the counter isn't on an instruction, i.e. for an empty program.
+    fn execute_one(&mut self, instructions: &[Instruction]) -> Result<Option<EndStatus>, Error> {
+        let instruction = instructions.get(self.counter).ok_or(Error::NoSolution)?;
+        self.visited[self.counter] = true;
+
+        match *instruction {
+            Instruction::Nop(_) => self.counter +=1,
+            Instruction::Acc(i) => {
+                self.accumulator += i;
+                self.counter +=1;
+            },
+            Instruction::Jump(i) => {
+                self.counter = (self.counter as isize + i) as usize
+            }, 
+        };
+
+        observe::step(|| self.frame(instructions));
+
+        Ok(if self.counter >= instructions.len() {
+            Some(EndStatus::Normal)
+        } else if self.visited[self.counter] {
+            Some(EndStatus::InfiniteLoop)
+        } else {
+            None
+        })
+    }
+
+    /// The instructions around the counter, with the visited ones marked `*`.
+    fn frame(&self, instructions: &[Instruction]) -> Frame {
+        let first = self.counter.saturating_sub(4);
+        let lines = (first..instructions.len().min(first + 9))
+            .map(|i| format!("{:>4} {} {}", i, if self.visited[i] { '*' } else { ' ' }, instructions[i]))
+            .collect();
+
+        Frame {
+            caption: format!("counter={} accumulator={}", self.counter, self.accumulator),
+            lines,
+            highlight: self.counter.checked_sub(first),
+        }
+    }
+}
+
+
+fn run_until_finished(instructions: &[Instruction], state: &mut ProgramState) -> Result<EndStatus, Error> {
+    loop {
+        cancel::check()?;
+
+        match state.execute_one(instructions)? {
+            None => continue,
+            Some(ended) => {
+                trace!(counter = state.counter, accumulator = state.accumulator, ?ended, "program ended");
+                return Ok(ended)
+            },
+        }
+    }
+}
+
+
+fn run_with_backtracking(instructions: &mut [Instruction], state: &mut ProgramState) -> Result<ProgramState, Error> {
+    // Run until encountering jmp, nop -> try switching that instruction and keep
+    // running, if we finish with EndStatus::Normal then all good, else backtrack
+    // to where we were, and keep the normal instruction. Then continue on
+    // and switch the next encountered jmp, nop instead. and try running the rest
+    // keep going like this until we get the program to execure normally
+    let mut ended: Option<EndStatus> = None;
+
+    while ended.is_none() {
+        cancel::check()?;
+
+        match instructions.get(state.counter).ok_or(Error::NoSolution)? {
+            Instruction::Nop(_) | Instruction::Jump(_) => {
+                let _attempt = debug_span!("attempt", counter = state.counter).entered();
+
+                // Swap instruction and run with cloned state
+                instructions[state.counter] = instructions[state.counter].swap();
+                let mut cloned_state = state.clone();
+                let status = run_until_finished(instructions, &mut cloned_state)?;
+                debug!(swapped = ?instructions[state.counter], ?status, "attempt finished");
+
+                if status == EndStatus::Normal {
+                    return Ok(cloned_state)
+                }
+                // Swap back
+                instructions[state.counter] = instructions[state.counter].swap();
+            },
+            _ => {},
+        }
+
+        ended = state.execute_one(instructions)?
+    }
+
+    Ok(state.clone())
+}
+
+
+#[aoc(day = 8, part = 1)]
+pub fn part_1(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let instructions: Vec<Instruction> = utils::parse_lines(input)?;
+    let mut state = ProgramState::new_with_capacity(instructions.len());
+
+    run_until_finished(&instructions, &mut state)?;
+
+    Ok(state.accumulator.into())
+}
+
+#[aoc(day = 8, part = 2)]
+pub fn part_2(input: &str, _params: &Params) -> Result<Answer, Error> {
+    let mut instructions: Vec<Instruction> = utils::parse_lines(input)?;
+    let mut state = ProgramState::new_with_capacity(instructions.len());
+    let resulting_state = run_with_backtracking(&mut instructions, &mut state)?;
+
+    Ok(resulting_state.accumulator.into())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_from_string() {
+        assert!(matches!("nop +0".parse(), Ok(Instruction::Nop(0))));
+        assert!(matches!("acc -99".parse(), Ok(Instruction::Acc(-99))));
+        assert!(matches!("jmp +4".parse(), Ok(Instruction::Jump(4))));
+
+        assert!("".parse::<Instruction>().is_err());
+        assert!("nop".parse::<Instruction>().is_err());
+        assert!("nop 4".parse::<Instruction>().is_err());
+        assert!("mul +4".parse::<Instruction>().is_err());
+        assert!("acc +-4".parse::<Instruction>().is_err());
+        assert!("n\u{e9}p +1".parse::<Instruction>().is_err());
+    }
+
+    #[test]
+    fn test_run_until_finished() {
+        let mut instructions = vec![
+            Instruction::Nop(0),
+            Instruction::Acc(1),
+            Instruction::Jump(4),
+            Instruction::Acc(3),
+            Instruction::Jump(-3),
+            Instruction::Acc(-99),
+            Instruction::Acc(1),
+            Instruction::Jump(-4),
+            Instruction::Acc(6),
+        ];
+        let mut state = ProgramState::new_with_capacity(instructions.len());
+
+        let status = run_until_finished(&instructions, &mut state).unwrap();
+
+        assert_eq!(status, EndStatus::InfiniteLoop);
+
+        // Changing this should make it terminate properly
+        instructions[7] = Instruction::Nop(-4);
+        state = ProgramState::new_with_capacity(instructions.len());
+        let status = run_until_finished(&instructions, &mut state).unwrap();
+
+        assert_eq!(status, EndStatus::Normal);
+        assert_eq!(state.accumulator, 8);
+    }
+
+    #[test]
+    fn test_run_with_backtracking() {
+        let mut instructions = vec![
+            Instruction::Nop(0),
+            Instruction::Acc(1),
+            Instruction::Jump(4),
+            Instruction::Acc(3),
+            Instruction::Jump(-3),
+            Instruction::Acc(-99),
+            Instruction::Acc(1),
+            Instruction::Jump(-4),
+            Instruction::Acc(6),
+        ];
+        let mut state = ProgramState::new_with_capacity(instructions.len());
+
+
+        let status = run_with_backtracking(&mut instructions, &mut state).unwrap();
+
+        assert_eq!(status.accumulator, 8);
+    }
+
+    #[test]
+    fn test_empty_program() {
+        let params = Params::default();
+
+        assert_eq!(part_1("", &params), Err(Error::NoSolution));
+        assert_eq!(part_2("", &params), Err(Error::NoSolution));
+    }
+
+    #[test]
+    fn test_example_snapshot() {
+        let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+        let instructions: Vec<Instruction> = utils::parse_lines(input).unwrap();
+        let params = Params::default();
+
+        insta::assert_debug_snapshot!("example_instructions", instructions);
+        insta::assert_debug_snapshot!("example_answers", (part_1(input, &params), part_2(input, &params)));
+    }
+}
@@ -0,0 +1,155 @@
+use aoc_core::utils;
+use aoc_core::answer::Answer;
+use aoc_core::error::Error;
+use aoc_core::hash::HashSet;
+use aoc_core::input;
+use aoc_core::params::{Param, Params};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use tracing::trace;
+use aoc_macros::aoc;
+
+
+pub const PARAMS: &[Param] = &[
+    Param { name: "preamble", default: "25", description: "Length of the preamble and of the window after it" },
+];
+
+
+fn first_non_allowed(numbers: impl IntoIterator<Item = i64>, preamble: usize) -> Option<i64> {
+    let mut numbers = numbers.into_iter();
+    let mut window: VecDeque<i64> = numbers.by_ref().take(preamble).collect();
+    if window.len() < preamble {
+        return None
+    }
+
+    let mut set: HashSet<i64> = window.iter().cloned().collect();
+
+    for (current, number) in numbers.enumerate() {
+        if !has_pair_sum(&set, number) {
+            return Some(number)
+        }
+        let removed = window.pop_front()?;
+        trace!(current = current + preamble, removed, inserted = number, "shift window");
+        set.remove(&removed);
+        set.insert(number);
+        window.push_back(number);
+    }
+
+    None
+}
+
+
+/// Scans the numbers a line at a time, so a file can be checked without
+/// reading all of it.
+pub fn first_invalid(reader: impl BufRead, preamble: usize) -> Result<Option<i64>, Error> {
+    utils::process_results(input::lines(reader), |numbers| first_non_allowed(numbers, preamble))
+}
+
+
+fn has_pair_sum(elements: &HashSet<i64>, sum: i64) -> bool {
+    for e in elements.iter() {
+        let comp = sum - e;
+        if comp != *e && elements.contains(&comp) {
+            return true
+        }
+    }
+
+    false
+}
+
+
+fn find_consecutive_sum(numbers: &[i64], total: i64) -> Option<(usize, usize)> {
+
+    for start in 0..numbers.len()-1 {
+        let mut sum = numbers[start];
+
+        for (i, number) in numbers.iter().enumerate().skip(start+1) {
+            sum += number;
+
+            if sum == total {
+                return Some((start, i+1));
+            } else if sum > total {
+                break;
+            }
+
+        }
+    }
+
+    None
+}
+
+
+
+#[aoc(day = 9, part = 1, params = PARAMS)]
+pub fn part_1(input: &str, params: &Params) -> Result<Answer, Error> {
+    let preamble: usize = params.get("preamble")?;
+    let part_1 = first_invalid(input.as_bytes(), preamble)?.ok_or(Error::NoSolution)?;
+
+    Ok(part_1.into())
+}
+
+#[aoc(day = 9, part = 2, params = PARAMS)]
+pub fn part_2(input: &str, params: &Params) -> Result<Answer, Error> {
+    let numbers: Vec<i64> = utils::parse_lines(input)?;
+    let preamble: usize = params.get("preamble")?;
+    let part_1 = first_non_allowed(numbers.iter().cloned(), preamble).ok_or(Error::NoSolution)?;
+    let (start, end) = find_consecutive_sum(&numbers, part_1).ok_or(Error::NoSolution)?;
+    let part_2 = numbers[start..end].iter().min().unwrap() + numbers[start..end].iter().max().unwrap();
+
+    Ok(part_2.into())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_it() {
+        let numbers: Vec<i64> = vec![
+            35,
+            20,
+            15,
+            25,
+            47,
+            40,
+            62,
+            55,
+            65,
+            95,
+            102,
+            117,
+            150,
+            182,
+            127,
+            219,
+            299,
+            277,
+            309,
+            576,
+        ];
+
+        let first = first_non_allowed(numbers.iter().cloned(), 5);
+
+        assert_eq!(first, Some(127));
+        assert_eq!(first_non_allowed(numbers[..3].iter().cloned(), 5), None);
+    }
+
+    #[test]
+    fn test_first_non_allowed_streaming() {
+        let reader = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219".as_bytes();
+
+        assert_eq!(first_invalid(reader, 5), Ok(Some(127)));
+        assert!(first_invalid("35\n\n20".as_bytes(), 1).is_err());
+    }
+
+    #[test]
+    fn test_example_snapshot() {
+        let input = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576\n";
+        let overrides = vec![("preamble".to_string(), "5".to_string())].into_iter().collect();
+        let params = Params::with_overrides(PARAMS, &overrides).unwrap();
+
+        insta::assert_debug_snapshot!("example_answers", (part_1(input, &params), part_2(input, &params)));
+    }
+}
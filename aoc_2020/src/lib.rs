@@ -0,0 +1,109 @@
+//! The 2020 solutions. Each day registers itself with `#[aoc]`, and which
+//! ones are compiled in is chosen with the `day_N` cargo features.
+
+aoc_core::solutions!();
+
+#[cfg(feature = "day_10")]
+pub mod day_10;
+#[cfg(feature = "day_9")]
+pub mod day_9;
+#[cfg(feature = "day_8")]
+pub mod day_8;
+#[cfg(feature = "day_7")]
+pub mod day_7;
+#[cfg(feature = "day_6")]
+pub mod day_6;
+#[cfg(feature = "day_5")]
+pub mod day_5;
+#[cfg(feature = "day_4")]
+pub mod day_4;
+#[cfg(feature = "day_3")]
+pub mod day_3;
+#[cfg(feature = "day_2")]
+pub mod day_2;
+#[cfg(feature = "day_1")]
+pub mod day_1;
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::registry::{days, get, implementations, Day, DEFAULT};
+
+    #[test]
+    fn test_days_in_order() {
+        let numbers: Vec<u8> = days().iter().map(|d| d.day).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn test_implementations() {
+        let all = implementations();
+
+        for (i, day) in all.iter().enumerate() {
+            let same_day: Vec<&Day> = all.iter().filter(|d| d.day == day.day).collect();
+
+            assert_eq!(same_day[0].name, DEFAULT);
+            assert_eq!(same_day.iter().filter(|d| d.name == day.name).count(), 1);
+            assert!(i == 0 || all[i - 1].day <= day.day);
+        }
+    }
+
+    #[cfg(feature = "day_7")]
+    #[test]
+    fn test_get() {
+        assert_eq!(get(7).map(|d| d.day), Some(7));
+        assert_eq!(get(7).map(|d| d.params.len()), Some(1));
+        assert!(get(26).is_none());
+
+        let names: Vec<&str> = implementations().iter().filter(|d| d.day == 7).map(|d| d.name).collect();
+        assert_eq!(names, vec![DEFAULT, "memoized"]);
+    }
+
+    /// Replays the fuzzing corpus the same way as the targets in `fuzz/`, so
+    /// the inputs that used to crash keep being handled.
+    #[cfg(all(feature = "day_2", feature = "day_4", feature = "day_5", feature = "day_7", feature = "day_8"))]
+    #[test]
+    fn test_fuzz_corpus() {
+        use aoc_core::params::Params;
+        use std::fs;
+        use std::path::Path;
+
+        let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fuzz/corpus");
+        let mut replayed = 0;
+
+        for target in fs::read_dir(&corpus).unwrap() {
+            let target = target.unwrap().path();
+            let name = target.file_name().unwrap().to_string_lossy().to_string();
+
+            for file in fs::read_dir(&target).unwrap() {
+                let data = fs::read(file.unwrap().path()).unwrap();
+                let s = match std::str::from_utf8(&data) {
+                    Ok(s) => s,
+                    Err(_) => continue,
+                };
+
+                match name.as_str() {
+                    "day_2_password" => drop(s.parse::<crate::day_2::Password>()),
+                    "day_4_passports" => drop(crate::day_4::get_passports(&s.split("\n\n").collect::<Vec<_>>())),
+                    "day_5_seat" => drop(s.parse::<crate::day_5::Seat>()),
+                    "day_7_rule" => drop(s.parse::<crate::day_7::Rule>()),
+                    "day_8_instruction" => drop(s.parse::<crate::day_8::Instruction>()),
+                    "all_days" => {
+                        for day in implementations() {
+                            let params = Params::defaults(day.params);
+                            let _ = ((day.part_1)(s, &params), (day.part_2)(s, &params));
+                        }
+                    },
+                    other => panic!("no fuzz target for the {} corpus", other),
+                }
+                replayed += 1;
+            }
+        }
+
+        assert!(replayed > 0);
+    }
+}
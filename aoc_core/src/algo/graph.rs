@@ -0,0 +1,503 @@
+//! A directed graph with weighted edges, and the searches the puzzles need.
+//!
+//! Nodes are any hashable value and are numbered in the order they're first
+//! added; the algorithms work on those numbers, which [`Graph::node`] maps
+//! back.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use crate::hash::HashMap;
+
+
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Self {
+        Self { nodes: vec![], indices: HashMap::default(), edges: vec![] }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of `node`, which is added if it isn't in the graph yet.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        self.nodes.push(node.clone());
+        self.edges.push(vec![]);
+        self.indices.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, weight: u64) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn index(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The edges leaving a node, as `(to, weight)`.
+    pub fn edges(&self, index: usize) -> &[(usize, u64)] {
+        &self.edges[index]
+    }
+
+    /// The same graph with every edge turned around.
+    pub fn reversed(&self) -> Self {
+        let mut edges = vec![vec![]; self.len()];
+        for (from, out) in self.edges.iter().enumerate() {
+            for &(to, weight) in out {
+                edges[to].push((from, weight));
+            }
+        }
+
+        Self { nodes: self.nodes.clone(), indices: self.indices.clone(), edges }
+    }
+}
+
+
+impl<N> Graph<N> {
+    /// The number of edges on the shortest path from `start` to each node.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.edges.len()];
+        let mut queue = VecDeque::from(vec![start]);
+        distances[start] = Some(0);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = distances[node].map(|d| d + 1);
+            for &(next, _) in &self.edges[node] {
+                if distances[next].is_none() {
+                    distances[next] = distance;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Every node reachable from `start`, in depth-first preorder.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.edges.len()];
+        let mut stack = vec![start];
+        let mut order = vec![];
+
+        while let Some(node) = stack.pop() {
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            order.push(node);
+            stack.extend(self.edges[node].iter().rev().map(|&(next, _)| next));
+        }
+
+        order
+    }
+
+    /// The total weight of the lightest path from `start` to each node. Paths
+    /// too heavy to weigh in a `u64` are left out.
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<u64>> {
+        let mut distances = vec![None; self.edges.len()];
+        let mut queue = BinaryHeap::from(vec![Reverse((0u64, start))]);
+
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if distances[node].is_some() {
+                continue;
+            }
+            distances[node] = Some(distance);
+            for &(next, weight) in &self.edges[node] {
+                if let (None, Some(distance)) = (distances[next], distance.checked_add(weight)) {
+                    queue.push(Reverse((distance, next)));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The lightest path from `start` to `goal` and its weight, guided by a
+    /// `heuristic` that must never overestimate the remaining weight. Like
+    /// [`dijkstra`](Self::dijkstra), paths too heavy for a `u64` are left out.
+    pub fn astar(&self, start: usize, goal: usize, heuristic: impl Fn(usize) -> u64) -> Option<(u64, Vec<usize>)> {
+        let mut best = vec![None; self.edges.len()];
+        let mut previous = vec![None; self.edges.len()];
+        let mut queue = BinaryHeap::from(vec![Reverse((heuristic(start), 0, start))]);
+        best[start] = Some(0);
+
+        while let Some(Reverse((_, distance, node))) = queue.pop() {
+            if node == goal {
+                let mut path = vec![goal];
+                while let Some(node) = previous[*path.last().unwrap()] {
+                    path.push(node);
+                }
+                path.reverse();
+                return Some((distance, path));
+            }
+            if best[node].is_some_and(|best| distance > best) {
+                continue;
+            }
+
+            for &(next, weight) in &self.edges[node] {
+                match distance.checked_add(weight) {
+                    Some(distance) if best[next].is_none_or(|best| distance < best) => {
+                        best[next] = Some(distance);
+                        previous[next] = Some(node);
+                        // Only the order matters here, so a heavy estimate can saturate
+                        queue.push(Reverse((distance.saturating_add(heuristic(next)), distance, next)));
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        None
+    }
+
+    /// All nodes with every edge pointing forward, or `None` if there's a
+    /// cycle.
+    pub fn toposort(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.edges.len()];
+        for &(to, _) in self.edges.iter().flatten() {
+            incoming[to] += 1;
+        }
+
+        let mut ready: Vec<usize> = (0..self.edges.len()).filter(|&n| incoming[n] == 0).collect();
+        let mut order = vec![];
+
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for &(next, _) in &self.edges[node] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        Some(order).filter(|o| o.len() == self.edges.len())
+    }
+
+    /// The strongly connected components, each listed after all components
+    /// it has edges into.
+    pub fn scc(&self) -> Vec<Vec<usize>> {
+        // Tarjan's algorithm, with the recursion on `calls` so a long chain
+        // can't overflow the stack
+        struct State {
+            index: Vec<Option<usize>>,
+            low: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            next: usize,
+            components: Vec<Vec<usize>>,
+        }
+
+        impl State {
+            fn open(&mut self, node: usize) {
+                self.index[node] = Some(self.next);
+                self.low[node] = self.next;
+                self.next += 1;
+                self.stack.push(node);
+                self.on_stack[node] = true;
+            }
+        }
+
+        let n = self.edges.len();
+        let mut s = State {
+            index: vec![None; n],
+            low: vec![0; n],
+            on_stack: vec![false; n],
+            stack: vec![],
+            next: 0,
+            components: vec![],
+        };
+
+        for root in 0..n {
+            if s.index[root].is_some() {
+                continue;
+            }
+            s.open(root);
+            let mut calls = vec![(root, 0)];
+
+            while let Some((node, edge)) = calls.pop() {
+                match self.edges[node].get(edge) {
+                    Some(&(next, _)) => {
+                        calls.push((node, edge + 1));
+                        match s.index[next] {
+                            None => {
+                                s.open(next);
+                                calls.push((next, 0));
+                            },
+                            Some(index) if s.on_stack[next] => s.low[node] = s.low[node].min(index),
+                            Some(_) => {},
+                        }
+                    },
+                    None => {
+                        if Some(s.low[node]) == s.index[node] {
+                            let mut component = vec![];
+                            while let Some(member) = s.stack.pop() {
+                                s.on_stack[member] = false;
+                                component.push(member);
+                                if member == node {
+                                    break;
+                                }
+                            }
+                            s.components.push(component);
+                        }
+                        // Back in the caller
+                        if let Some(&(parent, _)) = calls.last() {
+                            s.low[parent] = s.low[parent].min(s.low[node]);
+                        }
+                    },
+                }
+            }
+        }
+
+        s.components
+    }
+
+    /// The nodes reachable from `start`, each after every node it has edges
+    /// into, or `None` if there's a cycle among them.
+    fn postorder(&self, start: usize) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Open,
+            Done,
+        }
+
+        let mut marks = vec![Mark::New; self.edges.len()];
+        let mut stack = vec![(start, 0)];
+        let mut order = vec![];
+        marks[start] = Mark::Open;
+
+        while let Some((node, edge)) = stack.pop() {
+            match self.edges[node].get(edge) {
+                Some(&(next, _)) => {
+                    stack.push((node, edge + 1));
+                    match marks[next] {
+                        Mark::New => {
+                            marks[next] = Mark::Open;
+                            stack.push((next, 0));
+                        },
+                        Mark::Open => return None,
+                        Mark::Done => {},
+                    }
+                },
+                None => {
+                    marks[node] = Mark::Done;
+                    order.push(node);
+                },
+            }
+        }
+
+        Some(order)
+    }
+
+    /// For every node reachable from `start`, the number of paths from it to
+    /// `goal`, or `None` if a cycle is reachable or a count doesn't fit in a
+    /// `u64`. Each node is only counted
+    /// once, so this takes linear time even when there are exponentially
+    /// many paths.
+    pub fn path_counts(&self, start: usize, goal: usize) -> Option<Vec<u64>> {
+        self.path_counts_with(start, goal, |_, _| {})
+    }
+
+    /// [`path_counts`](Self::path_counts), calling `visit` with each node and
+    /// the counts so far as soon as that node's count is known.
+    pub fn path_counts_with(&self, start: usize, goal: usize, mut visit: impl FnMut(usize, &[u64])) -> Option<Vec<u64>> {
+        let mut counts = vec![0; self.edges.len()];
+
+        for node in self.postorder(start)? {
+            counts[node] = match node == goal {
+                true => 1,
+                false => self.edges[node].iter().try_fold(0u64, |sum, &(next, _)| sum.checked_add(counts[next]))?,
+            };
+            visit(node, &counts);
+        }
+
+        Some(counts)
+    }
+
+    pub fn count_paths(&self, start: usize, goal: usize) -> Option<u64> {
+        self.path_counts(start, goal).map(|counts| counts[start])
+    }
+
+    /// The paths of one or more edges leaving `start`, each counted as many
+    /// times as the product of its weights, or `None` if a cycle is reachable
+    /// or the count doesn't fit in a `u64`.
+    pub fn weighted_path_count(&self, start: usize) -> Option<u64> {
+        let mut counts = vec![0u64; self.edges.len()];
+
+        for node in self.postorder(start)? {
+            counts[node] = self.edges[node].iter().try_fold(0u64, |sum, &(next, weight)| {
+                counts[next].checked_add(1)?.checked_mul(weight)?.checked_add(sum)
+            })?;
+        }
+
+        Some(counts[start])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b -> d, a -> c -> d, d -> e, weighted so a, c, d is the
+    /// lightest way to d.
+    fn diamond() -> Graph<char> {
+        let mut graph = Graph::new();
+        graph.add_edge('a', 'b', 4);
+        graph.add_edge('a', 'c', 1);
+        graph.add_edge('b', 'd', 1);
+        graph.add_edge('c', 'd', 2);
+        graph.add_edge('d', 'e', 5);
+        graph
+    }
+
+    fn index(graph: &Graph<char>, node: char) -> usize {
+        graph.index(&node).unwrap()
+    }
+
+    #[test]
+    fn test_nodes() {
+        let mut graph = diamond();
+
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.add_node('c'), 2);
+        assert_eq!(graph.node(3), &'d');
+        assert_eq!(graph.index(&'x'), None);
+        assert_eq!(graph.reversed().edges(index(&graph, 'd')), &[(1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_search() {
+        let graph = diamond();
+
+        assert_eq!(graph.bfs(0), vec![Some(0), Some(1), Some(1), Some(2), Some(3)]);
+        assert_eq!(graph.bfs(4), vec![None, None, None, None, Some(0)]);
+        assert_eq!(graph.dfs(0), vec![0, 1, 3, 4, 2]);
+        assert_eq!(graph.dijkstra(0), vec![Some(0), Some(4), Some(1), Some(3), Some(8)]);
+        assert_eq!(graph.astar(0, 4, |_| 0), Some((8, vec![0, 2, 3, 4])));
+        assert_eq!(graph.astar(4, 0, |_| 0), None);
+    }
+
+    #[test]
+    fn test_toposort() {
+        let graph = diamond();
+        let order = graph.toposort().unwrap();
+        let position = |node| order.iter().position(|&n| n == index(&graph, node));
+
+        assert_eq!(order.len(), 5);
+        assert!(position('a') < position('b') && position('b') < position('d') && position('d') < position('e'));
+
+        let mut cyclic = graph;
+        cyclic.add_edge('e', 'a', 1);
+        assert_eq!(cyclic.toposort(), None);
+    }
+
+    #[test]
+    fn test_scc() {
+        let mut graph = diamond();
+        graph.add_edge('d', 'b', 1);
+
+        let mut components: Vec<Vec<char>> = graph
+            .scc()
+            .into_iter()
+            .map(|c| {
+                let mut nodes: Vec<char> = c.into_iter().map(|n| *graph.node(n)).collect();
+                nodes.sort();
+                nodes
+            })
+            .collect();
+        assert_eq!(components.first(), Some(&vec!['e']));
+        assert_eq!(components.last(), Some(&vec!['a']));
+
+        components.sort();
+        assert_eq!(components, vec![vec!['a'], vec!['b', 'd'], vec!['c'], vec!['e']]);
+    }
+
+    #[test]
+    fn test_path_counting() {
+        let mut graph = diamond();
+
+        assert_eq!(graph.count_paths(0, 4), Some(2));
+        assert_eq!(graph.path_counts(0, 3), Some(vec![2, 1, 1, 1, 0]));
+        assert_eq!(graph.count_paths(4, 0), Some(0));
+
+        let mut visited = vec![];
+        graph.path_counts_with(0, 3, |node, counts| visited.push((node, counts[node])));
+        // Each node once, after the nodes it has edges into
+        assert_eq!(visited, vec![(4, 0), (3, 1), (1, 1), (2, 1), (0, 2)]);
+
+        // a->b->d->e: 4*1*5, a->b->d: 4*1, a->b: 4, and the same through c
+        assert_eq!(graph.weighted_path_count(0), Some(20 + 4 + 4 + 10 + 2 + 1));
+
+        graph.add_edge('e', 'b', 1);
+        assert_eq!(graph.count_paths(0, 4), None);
+        assert_eq!(graph.weighted_path_count(2), None);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // 2^64 paths through 64 diamonds in a row
+        let mut diamonds = Graph::new();
+        for i in 0..64 {
+            for side in 1..=2 {
+                diamonds.add_edge(3 * i, 3 * i + side, 1);
+                diamonds.add_edge(3 * i + side, 3 * i + 3, 1);
+            }
+        }
+        let end = |i: usize| diamonds.index(&(3 * i)).unwrap();
+        assert_eq!(diamonds.count_paths(0, end(63)), Some(1 << 63));
+        assert_eq!(diamonds.count_paths(0, end(64)), None);
+
+        let mut heavy = Graph::new();
+        heavy.add_edge('a', 'b', u64::MAX);
+        heavy.add_edge('b', 'c', 1);
+        heavy.add_edge('a', 'c', u64::MAX);
+        assert_eq!(heavy.dijkstra(0), vec![Some(0), Some(u64::MAX), Some(u64::MAX)]);
+        assert_eq!(heavy.astar(0, 2, |_| 1), Some((u64::MAX, vec![0, 2])));
+        assert_eq!(heavy.weighted_path_count(0), None);
+
+        heavy.add_edge('c', 'd', 1);
+        assert_eq!(heavy.dijkstra(0)[3], None);
+        assert_eq!(heavy.astar(0, 3, |_| 0), None);
+    }
+
+    #[test]
+    fn test_scc_deep() {
+        let mut chain = Graph::new();
+        for i in 0..100_000 {
+            chain.add_edge(i, i + 1, 1);
+        }
+        chain.add_edge(100_000, 0, 1);
+
+        assert_eq!(chain.scc().len(), 1);
+    }
+}
@@ -0,0 +1,4 @@
+//! Algorithms shared between days.
+
+pub mod graph;
+pub mod number;
@@ -0,0 +1,232 @@
+//! Modular arithmetic on `i128` and `u128`. Products go through [`mulmod`],
+//! so nothing overflows as long as the moduli and results fit the types.
+
+use crate::hash::HashMap;
+
+
+pub fn gcd(a: i128, b: i128) -> i128 {
+    egcd(a, b).0
+}
+
+/// `(g, x, y)` with `a * x + b * y == g`, where `g` is the non-negative gcd.
+pub fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// The least common multiple, or `None` if it doesn't fit.
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    match gcd(a, b) {
+        0 => Some(0),
+        g => (a / g).checked_mul(b).map(i128::abs),
+    }
+}
+
+/// `a * b % m` without overflowing, however large the operands.
+pub fn mulmod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // Double and add, reducing at every step
+    let add = |x: u128, y: u128| if x >= m - y { x - (m - y) } else { x + y };
+    let (mut a, mut b, mut result) = (a % m, b % m, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add(result, a);
+        }
+        a = add(a, a);
+        b >>= 1;
+    }
+
+    result
+}
+
+pub fn modpow(base: u128, mut exp: u128, m: u128) -> u128 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mulmod(result, base, m);
+        }
+        base = mulmod(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn modinv(a: i128, m: i128) -> Option<i128> {
+    match egcd(a.rem_euclid(m), m) {
+        (1, x, _) => Some(x.rem_euclid(m)),
+        _ => None,
+    }
+}
+
+/// Combines congruences `x ≡ r (mod m)` into one `(r, m)` that holds for all
+/// of them, or `None` if they contradict each other. The moduli don't need
+/// to be coprime.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        let (g, p, _) = egcd(m1, m2);
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+
+        // x = r1 + m1 * t, where m1 * t ≡ r2 - r1 (mod m2)
+        let m = lcm(m1, m2)?;
+        let step = m2 / g;
+        let t = mulmod(((r2 - r1) / g).rem_euclid(step) as u128, p.rem_euclid(step) as u128, step as u128);
+        let r = (r1 as u128 + mulmod(m1 as u128, t, m as u128)) % m as u128;
+
+        Some((r as i128, m))
+    })
+}
+
+/// The smallest `x` with `base^x ≡ target (mod m)`, by baby-step giant-step.
+/// Takes about `sqrt(m)` time and memory, and needs `base` and `m` to be
+/// coprime.
+pub fn discrete_log(base: u128, target: u128, m: u128) -> Option<u128> {
+    if m == 1 {
+        return Some(0);
+    }
+    let target = target % m;
+    let n = m.isqrt() + 1;
+
+    let mut baby_steps = HashMap::default();
+    let mut power = 1;
+    for j in 0..n {
+        baby_steps.entry(power).or_insert(j);
+        power = mulmod(power, base, m);
+    }
+
+    // power is now base^n; each giant step divides by it
+    let giant_step = modinv(power as i128, m as i128)? as u128;
+    let mut gamma = target;
+    for i in 0..n {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * n + j);
+        }
+        gamma = mulmod(gamma, giant_step, m);
+    }
+
+    None
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_egcd() {
+        for a in -30..30 {
+            for b in -30..30 {
+                let (g, x, y) = egcd(a, b);
+                let brute = match (a, b) {
+                    (0, 0) => 0,
+                    _ => (1..=30).rev().find(|d| a % d == 0 && b % d == 0).unwrap(),
+                };
+
+                assert_eq!(g, brute, "gcd({}, {})", a, b);
+                assert_eq!(a * x + b * y, g);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lcm() {
+        for a in 1..30 {
+            for b in 1..30 {
+                let brute = (1..).find(|n| n % a == 0 && n % b == 0);
+                assert_eq!(lcm(a, b), brute);
+            }
+        }
+        assert_eq!(lcm(0, 5), Some(0));
+        assert_eq!(lcm(i128::MAX, 2), None);
+    }
+
+    #[test]
+    fn test_mulmod_modpow() {
+        let m = u128::MAX - 158;
+        assert_eq!(mulmod(m - 1, m - 1, m), 1);
+        assert_eq!(mulmod(u128::MAX, 2, m), 316);
+
+        for base in 0..20 {
+            for exp in 0..20 {
+                for m in 1..20 {
+                    let brute = (0..exp).fold(1 % m, |acc, _| acc * base % m);
+                    assert_eq!(modpow(base, exp, m), brute, "{}^{} mod {}", base, exp, m);
+                }
+            }
+        }
+        // Fermat's little theorem with a prime near 2^127
+        let p = (1 << 127) - 1;
+        assert_eq!(modpow(123456789, p - 1, p), 1);
+    }
+
+    #[test]
+    fn test_modinv() {
+        for m in 1..30i128 {
+            for a in -30..30 {
+                let brute = (0..m).find(|x| (a * x).rem_euclid(m) == 1 % m);
+                assert_eq!(modinv(a, m), brute, "{}^-1 mod {}", a, m);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        for m1 in 1..12 {
+            for m2 in 1..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let m = lcm(m1, m2).unwrap();
+                        let brute = (0..m).find(|x| x % m1 == r1 && x % m2 == r2).map(|x| (x, m));
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), brute);
+                    }
+                }
+            }
+        }
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+
+        // Moduli whose product is far beyond i64
+        let big = [(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009), (4, 999_999_937)];
+        let (r, m) = crt(&big).unwrap();
+        assert!(big.iter().all(|&(ri, mi)| r % mi == ri));
+        assert_eq!(m, 1_000_000_007 * 998_244_353 * 1_000_000_009 * 999_999_937);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        for m in 1..40 {
+            for base in 1..m {
+                if gcd(base as i128, m as i128) != 1 {
+                    continue;
+                }
+                for target in 0..m {
+                    let brute = (0..m).find(|&x| modpow(base, x, m) == target);
+                    assert_eq!(discrete_log(base, target, m), brute, "log_{} {} mod {}", base, target, m);
+                }
+            }
+        }
+        // The day 25 puzzle of 2020: 7^x ≡ 5764801 (mod 20201227)
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+    }
+}
@@ -0,0 +1,120 @@
+//! What a solver returns. Most answers are numbers, but some puzzles have
+//! text answers and some draw their answer as letters on a grid.
+
+use std::fmt;
+use crate::ocr;
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    String(String),
+    /// Rows of `#` (lit) and `.` (dark) cells.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// An answer given as text, e.g. by a plugin, which is an integer if it
+    /// reads as one.
+    pub fn from_text(text: &str) -> Self {
+        match text.parse() {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::String(text.to_string()),
+        }
+    }
+
+    /// A grid from its cells, row by row.
+    pub fn grid<R: AsRef<[bool]>>(cells: &[R]) -> Self {
+        let row = |r: &R| r.as_ref().iter().map(|&lit| if lit { '#' } else { '.' }).collect();
+        Answer::Grid(cells.iter().map(row).collect())
+    }
+
+    /// A grid's answer is the letters it spells, if they can be read.
+    pub fn letters(&self) -> Option<String> {
+        match self {
+            Answer::Grid(rows) => ocr::read(rows),
+            _ => None,
+        }
+    }
+
+    /// Whether this is the answer written in an answers file as `expected`.
+    pub fn matches(&self, expected: &str) -> bool {
+        self.to_string() == expected
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.to_string().contains('\n')
+    }
+}
+
+/// Integers and strings are shown as they are, and grids as the letters they
+/// spell or, if those can't be read, as the grid itself, one row per line.
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::String(s) => write!(f, "{}", s),
+            Answer::Grid(rows) => match ocr::read(rows) {
+                Some(letters) => write!(f, "{}", letters),
+                None => write!(f, "{}", rows.join("\n")),
+            },
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Integer(n as i128)
+            }
+        })*
+    };
+}
+
+from_integer!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::String(s.to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(1234usize).to_string(), "1234");
+        assert_eq!(Answer::from(-7i64).to_string(), "-7");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::from_text("42"), Answer::Integer(42));
+        assert_eq!(Answer::from_text("4 2"), Answer::String("4 2".to_string()));
+    }
+
+    #[test]
+    fn test_grid() {
+        let h = [
+            [true, false, false, true],
+            [true, false, false, true],
+            [true, true, true, true],
+            [true, false, false, true],
+            [true, false, false, true],
+            [true, false, false, true],
+        ];
+        assert_eq!(Answer::grid(&h).to_string(), "H");
+        assert!(!Answer::grid(&h).is_multiline());
+
+        let unreadable = Answer::grid(&[[true, false], [false, true]]);
+        assert_eq!(unreadable.to_string(), "#.\n.#");
+        assert!(unreadable.is_multiline());
+    }
+}
@@ -0,0 +1,153 @@
+//! A set of small integers stored as bits, for days that would otherwise
+//! build and intersect lots of tiny `HashSet`s.
+
+use std::fmt;
+use std::iter::FromIterator;
+
+
+/// Holds the integers `0..64 * N`, in `N` words. It's `Copy`, so sets can be
+/// combined without cloning.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const N: usize = 1> {
+    words: [u64; N],
+}
+
+impl<const N: usize> BitSet<N> {
+    pub const CAPACITY: usize = 64 * N;
+
+    pub const fn new() -> Self {
+        Self { words: [0; N] }
+    }
+
+    /// The set with every integer up to the capacity.
+    pub const fn full() -> Self {
+        Self { words: [u64::MAX; N] }
+    }
+
+    /// Adds `value`, returning whether it was new. Panics if `value` is
+    /// beyond the capacity.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::position(value);
+        let new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        new
+    }
+
+    /// Removes `value`, returning whether it was there.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::position(value);
+        let present = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.words[value / 64] & (1 << (value % 64)) != 0
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip(other, |a, b| a & b)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// The integers in the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || match rest {
+                0 => None,
+                _ => {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    Some(i * 64 + bit)
+                },
+            })
+        })
+    }
+
+    fn position(value: usize) -> (usize, u64) {
+        assert!(value < Self::CAPACITY, "{} doesn't fit in a BitSet of {}", value, Self::CAPACITY);
+        (value / 64, 1 << (value % 64))
+    }
+
+    fn zip(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = self.words;
+        for (word, &o) in words.iter_mut().zip(&other.words) {
+            *word = f(*word, o);
+        }
+        Self { words }
+    }
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<const N: usize> fmt::Debug for BitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set: BitSet<2> = BitSet::new();
+
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(127));
+        assert!(set.contains(127));
+        assert!(!set.contains(128));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(format!("{:?}", set), "{127}");
+        assert_eq!(BitSet::<2>::full().len(), 128);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_beyond_capacity() {
+        BitSet::<1>::new().insert(64);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: BitSet<2> = vec![1, 5, 64, 100].into_iter().collect();
+        let b: BitSet<2> = vec![5, 6, 100].into_iter().collect();
+
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![1, 5, 6, 64, 100]);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![5, 100]);
+        assert_eq!(a.intersection(&BitSet::full()), a);
+        assert!(a.intersection(&BitSet::new()).is_empty());
+    }
+}
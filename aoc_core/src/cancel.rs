@@ -0,0 +1,92 @@
+//! Cooperative cancellation, so a slow solver can't hang a whole run.
+//!
+//! Solvers can't be stopped from the outside, so loops and recursions that
+//! may run for long call [`check`] and give up with its error. The runner
+//! installs a [`Token`] with each part's time budget using [`with_token`];
+//! without one, `check` always passes.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::error::Error;
+
+
+/// Shared between the solver and whoever may cancel it, e.g. from another
+/// thread.
+#[derive(Debug, Clone, Default)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl Token {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token that also times out once `budget` has passed.
+    pub fn with_budget(budget: Duration) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Some((Instant::now() + budget, budget)),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(Error::Cancelled);
+        }
+        match self.deadline {
+            Some((deadline, budget)) if Instant::now() >= deadline => Err(Error::Timeout(budget)),
+            _ => Ok(()),
+        }
+    }
+}
+
+
+thread_local! {
+    static TOKEN: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with `token` checked by any solver it calls on this thread.
+pub fn with_token<R>(token: Token, f: impl FnOnce() -> R) -> R {
+    let previous = TOKEN.with(|t| t.replace(Some(token)));
+    let result = f();
+    TOKEN.with(|t| *t.borrow_mut() = previous);
+    result
+}
+
+/// Fails once the current token is cancelled or out of time.
+pub fn check() -> Result<(), Error> {
+    TOKEN.with(|t| t.borrow().as_ref().map_or(Ok(()), Token::check))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(), Ok(()));
+
+        let token = Token::new();
+        with_token(token.clone(), || {
+            assert_eq!(check(), Ok(()));
+            token.cancel();
+            assert_eq!(check(), Err(Error::Cancelled));
+        });
+        assert_eq!(check(), Ok(()));
+
+        let budget = Duration::from_millis(1);
+        with_token(Token::with_budget(budget), || {
+            std::thread::sleep(budget);
+            assert_eq!(check(), Err(Error::Timeout(budget)));
+        });
+    }
+}
@@ -0,0 +1,102 @@
+use std::char::ParseCharError;
+use std::convert::Infallible;
+use std::fmt;
+use std::num::{ParseFloatError, ParseIntError};
+use std::time::Duration;
+
+
+/// Returned by the input parsers when a line or block doesn't have the
+/// expected shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    reason: String,
+    input: String,
+}
+
+impl ParseError {
+    pub fn new(reason: &str, input: &str) -> Self {
+        Self {
+            reason: reason.to_string(),
+            input: input.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} in {:?}", self.reason, self.input)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+
+/// The `FromStr` errors of the types the line parsers read, as a
+/// [`ParseError`] for the line that failed. A `ParseError` is kept as it is,
+/// since it already says what was wrong.
+pub trait LineError {
+    fn for_line(self, line: &str) -> ParseError;
+}
+
+impl LineError for ParseError {
+    fn for_line(self, _line: &str) -> ParseError {
+        self
+    }
+}
+
+impl LineError for Infallible {
+    fn for_line(self, _line: &str) -> ParseError {
+        match self {}
+    }
+}
+
+macro_rules! impl_line_error {
+    ($($t:ty),*) => {
+        $(impl LineError for $t {
+            fn for_line(self, line: &str) -> ParseError {
+                ParseError::new(&self.to_string(), line)
+            }
+        })*
+    };
+}
+
+impl_line_error!(ParseIntError, ParseFloatError, ParseCharError);
+
+
+/// Why a solver didn't produce an answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    Io(String),
+    Param(String),
+    Plugin(String),
+    External(String),
+    Panic(String),
+    NoSolution,
+    Timeout(Duration),
+    Cancelled,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "parse error: {}", e),
+            Error::Io(e) => write!(f, "read error: {}", e),
+            Error::Param(e) => write!(f, "parameter error: {}", e),
+            Error::Plugin(e) => write!(f, "plugin error: {}", e),
+            Error::External(e) => write!(f, "external solver error: {}", e),
+            Error::Panic(e) => write!(f, "panicked: {}", e),
+            Error::NoSolution => write!(f, "no solution found"),
+            Error::Timeout(budget) => write!(f, "timed out after {:?}", budget),
+            Error::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
@@ -0,0 +1,421 @@
+//! Points on square and hex grids.
+//!
+//! Grids follow the puzzle inputs: `x` grows to the right and `y` grows down,
+//! so [`Direction::North`] is `(0, -1)` and turning clockwise goes from east
+//! to south.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use crate::error::ParseError;
+
+
+/// A point, or the offset between two points, in `N` dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Point([0; N]);
+
+    pub fn manhattan(&self, other: &Self) -> i64 {
+        self.0.iter().zip(&other.0).map(|(a, b)| (a - b).abs()).sum()
+    }
+
+    /// The `3^N - 1` points that differ by at most one in every coordinate.
+    pub fn neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..3usize.pow(N as u32)).filter_map(move |mut digits| {
+            let mut point = *self;
+            for coordinate in point.0.iter_mut() {
+                *coordinate += digits as i64 % 3 - 1;
+                digits /= 3;
+            }
+            Some(point).filter(|p| p != self)
+        })
+    }
+
+    /// The `2 * N` points one step away along a single axis.
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].iter().map(move |step| {
+                let mut point = *self;
+                point.0[axis] += step;
+                point
+            })
+        })
+    }
+}
+
+impl Point<2> {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point([x, y])
+    }
+
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    /// Turns the point a quarter turn clockwise around the origin, `turns`
+    /// times. Negative turns go counterclockwise.
+    pub fn rotate(&self, turns: i64) -> Self {
+        (0..turns.rem_euclid(4)).fold(*self, |p, _| Point2::new(-p.y(), p.x()))
+    }
+}
+
+impl Point<3> {
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point([x, y, z])
+    }
+}
+
+impl Point<4> {
+    pub const fn new(x: i64, y: i64, z: i64, w: i64) -> Self {
+        Point([x, y, z, w])
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, other: Self) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self * -1
+    }
+}
+
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    fn mul(mut self, factor: i64) -> Self {
+        for a in self.0.iter_mut() {
+            *a *= factor;
+        }
+        self
+    }
+}
+
+
+/// A heading on a square grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// In clockwise order, starting from north.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn delta(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new(0, -1),
+            Direction::East => Point2::new(1, 0),
+            Direction::South => Point2::new(0, 1),
+            Direction::West => Point2::new(-1, 0),
+        }
+    }
+
+    /// Turns a quarter turn clockwise `turns` times. Negative turns go
+    /// counterclockwise.
+    pub fn rotate(self, turns: i64) -> Self {
+        Self::ALL[(self as i64 + turns).rem_euclid(4) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Reads `N`, `E`, `S` or `W`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction::North),
+            'E' => Some(Direction::East),
+            'S' => Some(Direction::South),
+            'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl Add<Direction> for Point2 {
+    type Output = Self;
+
+    fn add(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+}
+
+impl AddAssign<Direction> for Point2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self += direction.delta();
+    }
+}
+
+impl Mul<i64> for Direction {
+    type Output = Point2;
+
+    fn mul(self, distance: i64) -> Point2 {
+        self.delta() * distance
+    }
+}
+
+
+/// A hex on a grid of pointy-topped hexes, in axial coordinates: `q` grows
+/// to the east and `r` to the south-east.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// The number of steps between two hexes.
+    pub fn distance(&self, other: &Self) -> i64 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        HexDirection::ALL.iter().map(move |&d| *self + d)
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Hex::new(self.q * factor, self.r * factor)
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    /// In clockwise order, starting from east.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    pub fn delta(self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::SouthEast => Hex::new(0, 1),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::NorthEast => Hex::new(1, -1),
+        }
+    }
+
+    /// Turns a sixth of a turn clockwise `turns` times.
+    pub fn rotate(self, turns: i64) -> Self {
+        Self::ALL[(self as i64 + turns).rem_euclid(6) as usize]
+    }
+
+    /// Reads a run of steps written without separators, e.g. `esenee`.
+    pub fn parse_path(s: &str) -> Result<Vec<Self>, ParseError> {
+        let mut steps = vec![];
+        let mut rest = s;
+
+        while !rest.is_empty() {
+            let (step, len) = match rest.as_bytes() {
+                [b'e', ..] => (HexDirection::East, 1),
+                [b'w', ..] => (HexDirection::West, 1),
+                [b's', b'e', ..] => (HexDirection::SouthEast, 2),
+                [b's', b'w', ..] => (HexDirection::SouthWest, 2),
+                [b'n', b'w', ..] => (HexDirection::NorthWest, 2),
+                [b'n', b'e', ..] => (HexDirection::NorthEast, 2),
+                _ => return Err(ParseError::new("invalid hex step", rest)),
+            };
+            steps.push(step);
+            rest = &rest[len..];
+        }
+
+        Ok(steps)
+    }
+}
+
+impl Add<HexDirection> for Hex {
+    type Output = Self;
+
+    fn add(self, direction: HexDirection) -> Self {
+        self + direction.delta()
+    }
+}
+
+impl AddAssign<HexDirection> for Hex {
+    fn add_assign(&mut self, direction: HexDirection) {
+        *self += direction.delta();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(4, 2);
+
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, -4));
+        assert_eq!(-a * 3, Point2::new(-3, 6));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(Point4::new(1, 1, 1, 1).manhattan(&Point4::ORIGIN), 4);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point3::new(0, 5, 0);
+
+        assert_eq!(Point2::ORIGIN.neighbours().count(), 8);
+        assert_eq!(p.neighbours().count(), 26);
+        assert!(p.neighbours().all(|n| n != p && n.0.iter().zip(&p.0).all(|(a, b)| (a - b).abs() <= 1)));
+        assert_eq!(Point4::ORIGIN.neighbours().count(), 80);
+
+        let orthogonal: Vec<Point2> = Point2::new(3, 3).orthogonal_neighbours().collect();
+        assert_eq!(orthogonal, vec![Point2::new(2, 3), Point2::new(4, 3), Point2::new(3, 2), Point2::new(3, 4)]);
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::East.turn_right(), Direction::South);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.reverse(), Direction::East);
+        assert_eq!(Direction::North.rotate(-7), Direction::East);
+        assert_eq!(Direction::from_char('S'), Some(Direction::South));
+
+        let mut ship = Point2::ORIGIN + Direction::East * 10;
+        ship += Direction::North;
+        assert_eq!(ship, Point2::new(10, -1));
+
+        // Turning a point matches turning the heading it points along
+        for &d in &Direction::ALL {
+            for turns in -5..5 {
+                assert_eq!((d.delta() * 7).rotate(turns), d.rotate(turns) * 7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        let path = HexDirection::parse_path("nwwswee").unwrap();
+        let end = path.iter().fold(Hex::ORIGIN, |hex, &step| hex + step);
+
+        assert_eq!(end, Hex::ORIGIN);
+        assert_eq!(HexDirection::parse_path("esew").unwrap().into_iter().fold(Hex::ORIGIN, Add::add), Hex::new(0, 1));
+        assert!(HexDirection::parse_path("enx").is_err());
+
+        assert_eq!(Hex::ORIGIN.neighbours().count(), 6);
+        assert!(Hex::ORIGIN.neighbours().all(|n| n.distance(&Hex::ORIGIN) == 1));
+        assert_eq!(Hex::new(3, -1).distance(&Hex::new(-2, 2)), 5);
+        assert_eq!(HexDirection::East.rotate(3), HexDirection::West);
+        assert_eq!(-(Hex::new(1, 2) - Hex::new(0, 1)) * 2, Hex::new(-2, -2));
+    }
+}
@@ -0,0 +1,13 @@
+//! The `HashMap` and `HashSet` the solvers use. They hash with SipHash, or
+//! with FxHash when the `fast-hash` feature is on.
+//!
+//! Create them with `default()` or `collect()` rather than `new()`, which
+//! only exists for the standard hasher.
+
+#[cfg(feature = "fast-hash")]
+pub type BuildHasher = rustc_hash::FxBuildHasher;
+#[cfg(not(feature = "fast-hash"))]
+pub type BuildHasher = std::collections::hash_map::RandomState;
+
+pub type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasher>;
+pub type HashSet<T> = std::collections::HashSet<T, BuildHasher>;
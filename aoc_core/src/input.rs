@@ -0,0 +1,156 @@
+//! Input sources that avoid holding more than one copy of a large input.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::str::{self, FromStr};
+use crate::error::{Error, LineError};
+
+
+/// A puzzle input, either read into memory or memory-mapped.
+pub enum Input {
+    Text(String),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Input {
+    pub fn read(path: &str) -> io::Result<Self> {
+        fs::read_to_string(path).map(Input::Text)
+    }
+
+    /// Maps the file instead of reading it, so only the pages the solver
+    /// touches are loaded. The file must not change while it's mapped.
+    #[cfg(feature = "mmap")]
+    pub fn map(path: &str) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        str::from_utf8(&map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Input::Mapped(map))
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Input::Text(text) => text,
+            // Checked to be UTF-8 in `map`
+            #[cfg(feature = "mmap")]
+            Input::Mapped(map) => unsafe { str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+
+/// Parses one line at a time from a reader, reusing a single buffer. Like
+/// [`iter_lines`](crate::utils::iter_lines), blank lines before the first line
+/// and after the last are skipped, while ones in between are parsed like any
+/// other line. Each line is trimmed.
+pub struct Lines<R, T> {
+    reader: R,
+    buffer: String,
+    started: bool,
+    /// Blank lines read since the last line, which are only passed on once
+    /// another line follows them.
+    blank: usize,
+    /// Whether `buffer` holds a line that wasn't passed on yet.
+    held: bool,
+    parsed: PhantomData<T>,
+}
+
+impl<R: BufRead, T: FromStr> Iterator for Lines<R, T>
+where
+    T::Err: LineError,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.held {
+                let line = match self.blank {
+                    0 => {
+                        self.held = false;
+                        self.buffer.trim()
+                    },
+                    _ => {
+                        self.blank -= 1;
+                        ""
+                    },
+                };
+                return Some(line.parse().map_err(|e: T::Err| e.for_line(line).into()));
+            }
+
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => {},
+                Err(e) => return Some(Err(Error::Io(e.to_string()))),
+            }
+
+            match (self.buffer.trim().is_empty(), self.started) {
+                (true, true) => self.blank += 1,
+                (true, false) => {},
+                (false, _) => {
+                    self.started = true;
+                    self.held = true;
+                },
+            }
+        }
+    }
+}
+
+pub fn lines<R: BufRead, T: FromStr>(reader: R) -> Lines<R, T> {
+    Lines { reader, buffer: String::new(), started: false, blank: 0, held: false, parsed: PhantomData }
+}
+
+pub fn stream_lines<T: FromStr>(path: &str) -> io::Result<Lines<io::BufReader<File>, T>> {
+    Ok(lines(io::BufReader::new(File::open(path)?)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use crate::utils;
+
+    #[test]
+    fn test_lines() {
+        let numbers: Vec<i64> = lines("1\n 2\r\n3".as_bytes()).collect::<Result<_, _>>().unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+
+        let mut invalid = lines::<_, i64>("1\nx\n".as_bytes());
+        assert_eq!(invalid.next(), Some(Ok(1)));
+        assert_eq!(invalid.next(), Some(Err(ParseError::new("invalid digit found in string", "x").into())));
+        assert_eq!(invalid.next(), None);
+    }
+
+    #[test]
+    fn test_lines_blank() {
+        let text = "\n\n1\n\n\n2\r\n3\n\n";
+        let streamed: Vec<Result<i64, Error>> = lines(text.as_bytes()).collect();
+        let split: Vec<Result<i64, Error>> = utils::iter_lines(text).map(|n| n.map_err(Error::from)).collect();
+
+        assert_eq!(streamed, split);
+        assert_eq!(streamed.len(), 5);
+    }
+
+    #[test]
+    fn test_input() {
+        let path = std::env::temp_dir().join(format!("aoc_core_input_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "1721\n979\n366\n299\n675\n1456\n").unwrap();
+
+        let text = Input::read(path).unwrap();
+        assert_eq!(stream_lines::<i64>(path).unwrap().count(), text.lines().count());
+
+        #[cfg(feature = "mmap")]
+        assert_eq!(&*Input::map(path).unwrap(), &*text);
+        fs::remove_file(path).unwrap();
+        assert!(Input::read(path).is_err());
+    }
+}
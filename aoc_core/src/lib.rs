@@ -0,0 +1,24 @@
+//! The parts of the Advent of Code setup that don't depend on the year: input
+//! parsing, errors, answers, the solver interface and shared algorithms.
+
+pub mod error;
+pub mod answer;
+pub mod ocr;
+pub mod cancel;
+pub mod algo;
+pub mod bitset;
+pub mod geom;
+pub mod hash;
+pub mod params;
+pub mod input;
+pub mod utils;
+pub mod observe;
+pub mod registry;
+
+// For the code `#[aoc]` generates, so solution crates don't need it too
+#[doc(hidden)]
+pub use linkme;
+// The regex `#[derive(RegexParse)]` compiles, which solution crates can use
+// for their own patterns as well
+#[cfg(feature = "regex")]
+pub use regex;
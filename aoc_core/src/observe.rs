@@ -0,0 +1,197 @@
+//! Lets solvers expose their intermediate states, e.g. for animations.
+//!
+//! Solvers call [`step`] with a closure building a [`Frame`]. The closure only
+//! runs while an observer is installed with [`with_observer`], so the hooks
+//! cost next to nothing in normal runs.
+
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+
+
+/// One intermediate state, drawn as lines of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub lines: Vec<String>,
+    pub highlight: Option<usize>,
+}
+
+pub trait Observer {
+    fn step(&mut self, frame: Frame);
+}
+
+
+thread_local! {
+    static OBSERVER: RefCell<Option<Rc<RefCell<dyn Observer>>>> = RefCell::new(None);
+}
+
+/// Runs `f` with `observer` receiving the frames of any solver it calls on
+/// this thread.
+pub fn with_observer<R>(observer: Rc<RefCell<dyn Observer>>, f: impl FnOnce() -> R) -> R {
+    let previous = OBSERVER.with(|o| o.replace(Some(observer)));
+    let result = f();
+    OBSERVER.with(|o| *o.borrow_mut() = previous);
+    result
+}
+
+pub fn step(frame: impl FnOnce() -> Frame) {
+    if let Some(observer) = OBSERVER.with(|o| o.borrow().clone()) {
+        observer.borrow_mut().step(frame());
+    }
+}
+
+
+/// Dumps the frames as plain text, marking the highlighted line with `>`.
+pub struct TextRecorder {
+    text: String,
+    frames: usize,
+    max_frames: usize,
+}
+
+impl TextRecorder {
+    pub fn new(max_frames: usize) -> Self {
+        Self { text: String::new(), frames: 0, max_frames }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Observer for TextRecorder {
+    fn step(&mut self, frame: Frame) {
+        if self.frames == self.max_frames {
+            return;
+        }
+        self.frames += 1;
+
+        let _ = writeln!(self.text, "--- {}: {}", self.frames, frame.caption);
+        for (i, line) in frame.lines.iter().enumerate() {
+            let marker = if frame.highlight == Some(i) { '>' } else { ' ' };
+            let _ = writeln!(self.text, "{} {}", marker, line);
+        }
+    }
+}
+
+
+const CHAR_WIDTH: f64 = 8.4;
+const LINE_HEIGHT: f64 = 16.0;
+
+/// Collects frames into a looping SVG animation.
+pub struct SvgRecorder {
+    frames: Vec<Frame>,
+    max_frames: usize,
+    frame_duration: f64,
+}
+
+impl SvgRecorder {
+    pub fn new(max_frames: usize, frame_duration: f64) -> Self {
+        Self { frames: Vec::new(), max_frames, frame_duration }
+    }
+
+    pub fn svg(&self) -> String {
+        let n = self.frames.len();
+        let columns = self.frames.iter().flat_map(|f| f.lines.iter().chain([&f.caption])).map(|l| l.chars().count()).max().unwrap_or(0);
+        let rows = self.frames.iter().map(|f| f.lines.len()).max().unwrap_or(0) + 1;
+        let (width, height) = ((columns as f64 + 2.0) * CHAR_WIDTH, (rows as f64 + 1.0) * LINE_HEIGHT);
+        let duration = n as f64 * self.frame_duration;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.0}\" height=\"{1:.0}\" viewBox=\"0 0 {0:.0} {1:.0}\" font-family=\"monospace\" font-size=\"14\">",
+            width, height
+        );
+        let _ = writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"#0f0f23\"/>");
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            // Discrete animation: visible only during this frame's slice of the loop
+            let (values, key_times) = match i {
+                0 => ("visible;hidden".to_string(), format!("0;{}", 1.0 / n as f64)),
+                _ => ("hidden;visible;hidden".to_string(), format!("0;{};{}", i as f64 / n as f64, (i + 1) as f64 / n as f64)),
+            };
+            let _ = writeln!(svg, "<g visibility=\"hidden\">");
+            let _ = writeln!(
+                svg,
+                "<animate attributeName=\"visibility\" values=\"{}\" keyTimes=\"{}\" dur=\"{}s\" calcMode=\"discrete\" repeatCount=\"indefinite\"/>",
+                values, key_times, duration
+            );
+            let _ = writeln!(svg, "<text x=\"{}\" y=\"{}\" fill=\"#ffff66\">{}</text>", CHAR_WIDTH, LINE_HEIGHT, escape(&frame.caption));
+
+            for (row, line) in frame.lines.iter().enumerate() {
+                let y = (row as f64 + 2.0) * LINE_HEIGHT;
+                if frame.highlight == Some(row) {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"0\" y=\"{}\" width=\"{:.0}\" height=\"{}\" fill=\"#333366\"/>",
+                        y - LINE_HEIGHT + 4.0, width, LINE_HEIGHT
+                    );
+                }
+                let _ = writeln!(svg, "<text x=\"{}\" y=\"{}\" fill=\"#cccccc\" xml:space=\"preserve\">{}</text>", CHAR_WIDTH, y, escape(line));
+            }
+            let _ = writeln!(svg, "</g>");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl Observer for SvgRecorder {
+    fn step(&mut self, frame: Frame) {
+        if self.frames.len() < self.max_frames {
+            self.frames.push(frame);
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(caption: &str, highlight: Option<usize>) -> Frame {
+        Frame {
+            caption: caption.to_string(),
+            lines: vec!["ab".to_string(), "<c>".to_string()],
+            highlight,
+        }
+    }
+
+    #[test]
+    fn test_with_observer() {
+        let recorder = Rc::new(RefCell::new(TextRecorder::new(2)));
+
+        step(|| panic!("No observer installed, so the frame isn't built"));
+        with_observer(recorder.clone(), || {
+            step(|| frame("first", Some(1)));
+            step(|| frame("second", None));
+            step(|| frame("third", None));
+        });
+        step(|| panic!("Observer is removed again"));
+
+        assert_eq!(recorder.borrow().text(), "--- 1: first\n  ab\n> <c>\n--- 2: second\n  ab\n  <c>\n");
+    }
+
+    #[test]
+    fn test_svg_recorder() {
+        let mut recorder = SvgRecorder::new(10, 0.5);
+        recorder.step(frame("first", Some(0)));
+        recorder.step(frame("second", None));
+
+        let svg = recorder.svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<animate ").count(), 2);
+        assert!(svg.contains("values=\"visible;hidden\" keyTimes=\"0;0.5\" dur=\"1s\""));
+        assert!(svg.contains("values=\"hidden;visible;hidden\" keyTimes=\"0;0.5;1\""));
+        assert!(svg.contains("&lt;c&gt;"));
+        assert_eq!(svg.matches("fill=\"#333366\"").count(), 1);
+    }
+}
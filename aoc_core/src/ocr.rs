@@ -0,0 +1,120 @@
+//! Reads the letters that some puzzles draw instead of giving a number, in
+//! the 4x6 font the puzzles use (Y is 5 wide), e.g.
+//!
+//! ```text
+//! ###..#..#.###.
+//! #..#.#..#.#..#
+//! #..#.#..#.#..#
+//! ###..#..#.###.
+//! #.#..#..#.#...
+//! #..#..##..#...
+//! ```
+//!
+//! spells `RUP`.
+
+
+pub const HEIGHT: usize = 6;
+
+const GLYPHS: [(char, [&str; HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+
+/// The lit cells of each column, as a bit per row.
+fn columns<S: AsRef<str>>(rows: &[S]) -> Vec<u8> {
+    let width = rows.iter().map(|r| r.as_ref().chars().count()).max().unwrap_or(0);
+    let mut columns = vec![0; width];
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.as_ref().chars().enumerate() {
+            if c == '#' {
+                columns[x] |= 1 << y;
+            }
+        }
+    }
+
+    columns
+}
+
+/// Splits columns into letters at the empty columns between them.
+fn letters(columns: &[u8]) -> Vec<&[u8]> {
+    columns.split(|&c| c == 0).filter(|l| !l.is_empty()).collect()
+}
+
+/// The letters spelled by a grid of `#` (lit) and `.` (dark) rows, or `None`
+/// if it isn't six rows high or has a shape that isn't in the font.
+pub fn read<S: AsRef<str>>(rows: &[S]) -> Option<String> {
+    if rows.len() != HEIGHT {
+        return None;
+    }
+
+    let glyphs: Vec<(char, Vec<u8>)> = GLYPHS
+        .iter()
+        .map(|(letter, glyph)| (*letter, letters(&columns(glyph)).concat()))
+        .collect();
+
+    let text: Option<String> = letters(&columns(rows))
+        .into_iter()
+        .map(|shape| glyphs.iter().find(|(_, glyph)| glyph == shape).map(|(letter, _)| *letter))
+        .collect();
+
+    text.filter(|t| !t.is_empty())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let rows = [
+            "###..#..#.###.",
+            "#..#.#..#.#..#",
+            "#..#.#..#.#..#",
+            "###..#..#.###.",
+            "#.#..#..#.#...",
+            "#..#..##..#...",
+        ];
+        assert_eq!(read(&rows), Some("RUP".to_string()));
+
+        // Y is a column wider than the other letters
+        let rows = [
+            "###..#...#",
+            "#..#.#...#",
+            "#..#..#.#.",
+            "###....#..",
+            "#......#..",
+            "#......#..",
+        ];
+        assert_eq!(read(&rows), Some("PY".to_string()));
+
+        for (letter, glyph) in &GLYPHS {
+            assert_eq!(read(glyph), Some(letter.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_unreadable() {
+        assert_eq!(read(&["#..#", "####"]), None);
+        assert_eq!(read(&["....", "....", "....", "....", "....", "...."]), None);
+        assert_eq!(read(&["#...", "##..", "#.#.", "#..#", "#...", "#..."]), None);
+    }
+}
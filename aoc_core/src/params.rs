@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use crate::error::{Error, ParseError};
+
+
+/// A puzzle constant a day exposes so it can be changed without recompiling.
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+
+/// Parameter values for one day: the declared defaults plus any overrides.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: HashMap<String, String>,
+    overridden: bool,
+}
+
+impl Params {
+    pub fn defaults(declared: &[Param]) -> Self {
+        Self {
+            values: declared.iter().map(|p| (p.name.to_string(), p.default.to_string())).collect(),
+            overridden: false,
+        }
+    }
+
+    pub fn with_overrides(declared: &[Param], overrides: &HashMap<String, String>) -> Result<Self, Error> {
+        let mut params = Self::defaults(declared);
+
+        for (name, value) in overrides {
+            let current = params.values.get_mut(name).ok_or_else(|| Error::Param(format!("unknown parameter {:?}", name)))?;
+            // Spacing doesn't matter, as values are parsed as words
+            if !current.split_whitespace().eq(value.split_whitespace()) {
+                *current = value.clone();
+                params.overridden = true;
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Whether any value differs from the defaults, in which case the known
+    /// answers no longer apply.
+    pub fn is_overridden(&self) -> bool {
+        self.overridden
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, Error> {
+        let value = self.raw(name)?;
+        value.parse().map_err(|_| Error::Param(format!("invalid value {:?} for {}", value, name)))
+    }
+
+    /// A whitespace separated list, e.g. `slopes = 1,1 3,1`.
+    pub fn get_list<T: FromStr>(&self, name: &str) -> Result<Vec<T>, Error> {
+        let value = self.raw(name)?;
+        value
+            .split_whitespace()
+            .map(|v| v.parse().map_err(|_| Error::Param(format!("invalid value {:?} in {}", v, name))))
+            .collect()
+    }
+
+    fn raw(&self, name: &str) -> Result<&str, Error> {
+        self.values
+            .get(name)
+            .map(|v| v.as_str())
+            .ok_or_else(|| Error::Param(format!("undeclared parameter {:?}", name)))
+    }
+}
+
+
+/// Parameter overrides and time budgets for all days, read from a config file
+/// and `--param`.
+///
+/// The config file has one section per day, where `timeout` is the budget in
+/// seconds for both parts and `part_N.timeout` for only one:
+///
+/// ```text
+/// [day_7]
+/// bag = "shiny gold"
+/// part_2.timeout = 0.5
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    overrides: HashMap<u8, HashMap<String, String>>,
+    timeouts: HashMap<(u8, Option<u8>), Duration>,
+    default_timeout: Option<Duration>,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut config = Self::default();
+        let mut day = None;
+
+        for line in text.lines().map(|l| l.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                day = Some(parse_day(section).ok_or_else(|| ParseError::new("invalid section", line))?);
+            } else {
+                let day = day.ok_or_else(|| ParseError::new("parameter outside of a [day_N] section", line))?;
+                let (name, value) = line.split_once('=').ok_or_else(|| ParseError::new("expected name = value", line))?;
+                config.insert(day, name.trim(), unquote(value.trim())).map_err(|reason| ParseError::new(reason, line))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Applies a `day_N.name=value` override.
+    pub fn set(&mut self, assignment: &str) -> Result<(), ParseError> {
+        let invalid = || ParseError::new("expected day_N.name=value", assignment);
+        let (key, value) = assignment.split_once('=').ok_or_else(invalid)?;
+        let (day, name) = key.split_once('.').ok_or_else(invalid)?;
+
+        self.insert(parse_day(day).ok_or_else(invalid)?, name, value)
+            .map_err(|reason| ParseError::new(reason, assignment))
+    }
+
+    /// The budget for parts that don't have one of their own.
+    pub fn set_default_timeout(&mut self, budget: Duration) {
+        self.default_timeout = Some(budget);
+    }
+
+    pub fn params(&self, day: u8, declared: &[Param]) -> Result<Params, Error> {
+        match self.overrides.get(&day) {
+            Some(overrides) => Params::with_overrides(declared, overrides),
+            None => Ok(Params::defaults(declared)),
+        }
+    }
+
+    /// The day's parameters with `overrides` on top of the configured ones.
+    pub fn params_with(&self, day: u8, declared: &[Param], overrides: &HashMap<String, String>) -> Result<Params, Error> {
+        let mut merged = self.overrides.get(&day).cloned().unwrap_or_default();
+        merged.extend(overrides.iter().map(|(name, value)| (name.clone(), value.clone())));
+
+        Params::with_overrides(declared, &merged)
+    }
+
+    pub fn timeout(&self, day: u8, part: u8) -> Option<Duration> {
+        self.timeouts
+            .get(&(day, Some(part)))
+            .or_else(|| self.timeouts.get(&(day, None)))
+            .copied()
+            .or(self.default_timeout)
+    }
+
+    fn insert(&mut self, day: u8, name: &str, value: &str) -> Result<(), &'static str> {
+        let part = match (name, name.strip_prefix("part_").and_then(|n| n.strip_suffix(".timeout"))) {
+            ("timeout", _) => None,
+            (_, Some(part)) => Some(parse_part(part).ok_or("expected timeout or part_N.timeout")?),
+            (_, None) => {
+                self.overrides.entry(day).or_default().insert(name.to_string(), value.to_string());
+                return Ok(());
+            },
+        };
+        let budget = value.parse().ok().and_then(|s| Duration::try_from_secs_f64(s).ok()).ok_or("invalid timeout")?;
+
+        self.timeouts.insert((day, part), budget);
+        Ok(())
+    }
+}
+
+fn parse_day(s: &str) -> Option<u8> {
+    s.strip_prefix("day_")?.parse().ok()
+}
+
+fn parse_part(s: &str) -> Option<u8> {
+    s.parse().ok().filter(|p| (1..=2).contains(p))
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param { name: "target", default: "2020", description: "" },
+        Param { name: "bag", default: "shiny gold", description: "" },
+        Param { name: "read_timeout", default: "1", description: "" },
+    ];
+
+    #[test]
+    fn test_params() {
+        let params = Params::defaults(DECLARED);
+
+        assert_eq!(params.get::<i64>("target"), Ok(2020));
+        assert_eq!(params.get::<String>("bag"), Ok("shiny gold".to_string()));
+        assert!(params.get::<i64>("bag").is_err());
+        assert!(params.get::<i64>("other").is_err());
+        assert!(!params.is_overridden());
+    }
+
+    #[test]
+    fn test_config() {
+        let mut config = Config::parse("# Variants\n[day_1]\ntarget = 100\n\n[day_7]\nbag = \"dark red\"\n").unwrap();
+        config.set("day_1.target=200").unwrap();
+
+        let day_1 = config.params(1, DECLARED).unwrap();
+        let day_7 = config.params(7, DECLARED).unwrap();
+        let day_9 = config.params(9, DECLARED).unwrap();
+
+        assert_eq!(day_1.get::<i64>("target"), Ok(200));
+        assert_eq!(day_7.get::<String>("bag"), Ok("dark red".to_string()));
+        assert!(day_7.is_overridden());
+        assert_eq!(day_9, Params::defaults(DECLARED));
+
+        let overrides = [("bag".to_string(), "dim tan".to_string())].iter().cloned().collect();
+        let day_1 = config.params_with(1, DECLARED, &overrides).unwrap();
+        assert_eq!((day_1.get::<i64>("target"), day_1.get::<String>("bag")), (Ok(200), Ok("dim tan".to_string())));
+
+        config.set("day_9.bag=shiny  gold").unwrap();
+        assert!(!config.params(9, DECLARED).unwrap().is_overridden());
+        config.set("day_9.read_timeout=2").unwrap();
+        assert_eq!(config.params(9, DECLARED).unwrap().get::<u8>("read_timeout"), Ok(2));
+        assert_eq!(config.timeout(9, 1), None);
+    }
+
+    #[test]
+    fn test_config_invalid() {
+        assert!(Config::parse("target = 1").is_err());
+        assert!(Config::parse("[day_x]").is_err());
+        assert!(Config::parse("[day_1]\ntarget").is_err());
+        assert!(Config::default().set("target=1").is_err());
+
+        let mut config = Config::default();
+        config.set("day_1.unknown=1").unwrap();
+        assert!(config.params(1, DECLARED).is_err());
+    }
+
+    #[test]
+    fn test_get_list() {
+        let mut config = Config::default();
+        config.set("day_1.target=1 2  3").unwrap();
+        let params = config.params(1, DECLARED).unwrap();
+
+        assert_eq!(params.get_list::<u8>("target"), Ok(vec![1, 2, 3]));
+        assert!(params.get_list::<char>("target").is_ok());
+        assert!(Params::defaults(DECLARED).get_list::<u8>("bag").is_err());
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let mut config = Config::parse("[day_7]\ntimeout = 2\npart_2.timeout = 0.5\n").unwrap();
+        config.set("day_8.part_1.timeout=3").unwrap();
+
+        assert_eq!(config.timeout(7, 1), Some(Duration::from_secs(2)));
+        assert_eq!(config.timeout(7, 2), Some(Duration::from_millis(500)));
+        assert_eq!(config.timeout(8, 1), Some(Duration::from_secs(3)));
+        assert_eq!(config.timeout(8, 2), None);
+        assert_eq!(config.params(7, DECLARED), Ok(Params::defaults(DECLARED)));
+
+        config.set_default_timeout(Duration::from_secs(10));
+        assert_eq!(config.timeout(8, 2), Some(Duration::from_secs(10)));
+
+        assert!(Config::parse("[day_7]\ntimeout = soon").is_err());
+        assert!(Config::parse("[day_7]\npart_3.timeout = 1").is_err());
+        assert!(Config::default().set("day_7.timeout=-1").is_err());
+    }
+}
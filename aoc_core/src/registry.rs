@@ -0,0 +1,119 @@
+//! How solvers reach the runner. Solution crates mark their part functions
+//! with `#[aoc(day = N, part = P)]` from `aoc_macros`, which adds them to
+//! [`PARTS`] at compile time, and [`implementations`] pairs them up into
+//! [`Day`]s.
+
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::params::{Param, Params};
+use linkme::distributed_slice;
+
+/// Solves one part of a puzzle from the raw input text.
+pub type Solver = &'static (dyn Fn(&str, &Params) -> Result<Answer, Error> + Sync);
+
+/// Name of the main implementation of each day. Alternative implementations
+/// and plugins have names of their own.
+pub const DEFAULT: &str = "default";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Builtin,
+    Plugin,
+    External,
+}
+
+/// An implementation of a puzzle day, either compiled into a solutions crate,
+/// loaded from a plugin or run as an external command.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub name: &'static str,
+    pub source: Source,
+    pub part_1: Solver,
+    pub part_2: Solver,
+    pub params: &'static [Param],
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<Solver> {
+        match part {
+            1 => Some(self.part_1),
+            2 => Some(self.part_2),
+            _ => None,
+        }
+    }
+}
+
+
+
+/// One part of a day, as registered by the `#[aoc]` attribute.
+pub struct Part {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub solver: Solver,
+    pub params: &'static [Param],
+}
+
+/// Every registered part, in no particular order.
+#[distributed_slice]
+pub static PARTS: [Part];
+
+
+/// Implemented by `#[aoc]` and `#[aoc_generator]` on the solution crate's
+/// marker type, declared with [`solutions!`](crate::solutions), once per
+/// part. Two registrations of the same part are then conflicting impls, and
+/// [`assert_registered`] fails to compile for one that's missing. `NAME` is a
+/// hash of the implementation name, and generators register as part 0.
+#[diagnostic::on_unimplemented(
+    message = "part {PART} of day {DAY} has no `#[aoc]` function",
+    note = "each implementation needs both parts, registered with the same `name`"
+)]
+pub trait Registered<const DAY: u8, const PART: u8, const NAME: u64> {}
+
+#[doc(hidden)]
+pub fn assert_registered<T: Registered<DAY, PART, NAME>, const DAY: u8, const PART: u8, const NAME: u64>() {}
+
+/// Declares the marker type the `#[aoc]` attributes register parts on. A
+/// solution crate invokes it once, at its root.
+#[macro_export]
+macro_rules! solutions {
+    () => {
+        #[doc(hidden)]
+        pub struct AocSolutions;
+    };
+}
+
+
+/// The main implementation of every day.
+pub fn days() -> Vec<Day> {
+    implementations().into_iter().filter(|d| d.name == DEFAULT).collect()
+}
+
+/// Every implementation of every registered day, ordered by day with the
+/// main one first. The parameters are those given to part 1, or to part 2 if
+/// part 1 has none.
+pub fn implementations() -> Vec<Day> {
+    let mut days: Vec<Day> = PARTS
+        .iter()
+        .filter(|p| p.part == 1)
+        .filter_map(|p1| {
+            let p2 = PARTS.iter().find(|p| p.part == 2 && p.day == p1.day && p.name == p1.name)?;
+            Some(Day {
+                day: p1.day,
+                name: p1.name,
+                source: Source::Builtin,
+                part_1: p1.solver,
+                part_2: p2.solver,
+                params: if p1.params.is_empty() { p2.params } else { p1.params },
+            })
+        })
+        .collect();
+
+    days.sort_by_key(|d| (d.day, d.name != DEFAULT, d.name));
+    days
+}
+
+pub fn get(day: u8) -> Option<Day> {
+    days().into_iter().find(|d| d.day == day)
+}
@@ -0,0 +1,56 @@
+use std::str::FromStr;
+use crate::error::{LineError, ParseError};
+
+pub fn parse_lines<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: LineError,
+{
+    iter_lines(text).collect()
+}
+
+/// Like `parse_lines`, for solvers that don't need all lines at once.
+pub fn iter_lines<T: FromStr>(text: &str) -> impl Iterator<Item = Result<T, ParseError>> + '_
+where
+    T::Err: LineError,
+{
+    text
+        .trim()
+        .lines()
+        .map(|x| x.parse().map_err(|e: T::Err| e.for_line(x)))
+}
+
+/// Runs `f` on the values up to the first error, and returns that error if
+/// there was one.
+pub fn process_results<T, E, R>(
+    results: impl Iterator<Item = Result<T, E>>,
+    f: impl FnOnce(&mut dyn Iterator<Item = T>) -> R,
+) -> Result<R, E> {
+    let mut error = None;
+    let result = f(&mut results.map_while(|r| r.map_err(|e| error = Some(e)).ok()));
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// 64 bit FNV-1a, stable across builds so it can identify an input file.
+pub fn hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_results() {
+        let sum = |text| process_results(iter_lines::<i64>(text), |numbers| numbers.sum::<i64>());
+
+        assert_eq!(sum("1\n2\n3\n"), Ok(6));
+        assert_eq!(sum("1\nx\n3"), Err(ParseError::new("invalid digit found in string", "x")));
+    }
+}
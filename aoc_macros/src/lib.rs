@@ -0,0 +1,270 @@
+//! Attributes that register solutions with `aoc_core::registry`, so adding a
+//! day only takes its module.
+//!
+//! ```ignore
+//! #[aoc_generator(day = 7)]
+//! fn parse_rules(input: &str) -> Result<Vec<Rule>, ParseError> { ... }
+//!
+//! #[aoc(day = 7, part = 1, params = PARAMS)]
+//! fn part_1(rules: &[Rule], params: &Params) -> Result<usize, Error> { ... }
+//! ```
+//!
+//! A part takes the raw input as `&str`, or a reference to what the day's
+//! generator returns, optionally followed by the `&Params`. It returns
+//! anything that converts into an `Answer`, or a `Result` of one. The crate
+//! root has to invoke `aoc_core::solutions!()` once.
+//!
+//! `#[derive(RegexParse)]`, behind the `regex` feature, writes the `FromStr`
+//! of a struct whose fields are the groups of a regex, so parsers don't have
+//! to.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Error, Expr, FnArg, GenericArgument, ItemFn, LitInt, LitStr, PathArguments, ReturnType, Type};
+
+#[cfg(feature = "regex")]
+mod regex_parse;
+
+
+/// Registers a function as one part of a day. Takes `day`, `part` (1 or 2),
+/// and optionally the `name` of an alternative implementation and the
+/// `params` it reads.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attributes = Attributes::default();
+    let parser = syn::meta::parser(|meta| attributes.parse(meta, true));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    expand_part(attributes, &function)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Registers a function that parses a day's input, for the parts whose first
+/// argument isn't `&str`. Takes `day`.
+#[proc_macro_attribute]
+pub fn aoc_generator(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attributes = Attributes::default();
+    let parser = syn::meta::parser(|meta| attributes.parse(meta, false));
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    expand_generator(attributes, &function)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `FromStr` by matching the `#[regex(...)]` pattern and parsing
+/// each group into a field, with `ParseError`s for lines that don't match
+/// and groups that don't parse. The groups go to the fields in order or, if
+/// the pattern names them, by name, and the regex is compiled on first use.
+/// `Option` fields are `None` when their group doesn't take part in the
+/// match. Needs the `regex` features of this crate and `aoc_core`.
+///
+/// ```ignore
+/// #[derive(RegexParse)]
+/// #[regex(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$")]
+/// pub struct Password(usize, usize, char, String);
+/// ```
+#[cfg(feature = "regex")]
+#[proc_macro_derive(RegexParse, attributes(regex))]
+pub fn derive_regex_parse(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+
+    regex_parse::expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+
+#[derive(Default)]
+struct Attributes {
+    day: Option<u8>,
+    part: Option<u8>,
+    name: Option<String>,
+    params: Option<Expr>,
+}
+
+impl Attributes {
+    fn parse(&mut self, meta: ParseNestedMeta, is_part: bool) -> syn::Result<()> {
+        if meta.path.is_ident("day") {
+            let day: LitInt = meta.value()?.parse()?;
+            match day.base10_parse()? {
+                n @ 1..=25 => self.day = Some(n),
+                _ => return Err(Error::new(day.span(), "days go from 1 to 25")),
+            }
+        } else if is_part && meta.path.is_ident("part") {
+            let part: LitInt = meta.value()?.parse()?;
+            match part.base10_parse()? {
+                n @ 1..=2 => self.part = Some(n),
+                _ => return Err(Error::new(part.span(), "the part is 1 or 2")),
+            }
+        } else if is_part && meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if is_part && meta.path.is_ident("params") {
+            self.params = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unknown argument"));
+        }
+
+        Ok(())
+    }
+}
+
+
+fn expand_part(attributes: Attributes, function: &ItemFn) -> syn::Result<TokenStream2> {
+    let day = attributes.day.ok_or_else(|| Error::new(Span::call_site(), "missing `day = N`"))?;
+    let part = attributes.part.ok_or_else(|| Error::new(Span::call_site(), "missing `part = 1` or `part = 2`"))?;
+    let name = attributes.name.unwrap_or_else(|| "default".to_string());
+    let params = attributes.params.map_or_else(|| quote!(&[]), |p| quote!(#p));
+
+    let ident = &function.sig.ident;
+    let inputs = &function.sig.inputs;
+    let call = match inputs.len() {
+        1 => quote!(#ident(input)),
+        2 => quote!(#ident(input, params)),
+        _ => return Err(Error::new(inputs.span(), "expected the input, optionally followed by `&Params`")),
+    };
+    let input = match takes_raw_input(&inputs[0]) {
+        true => quote!(),
+        false => {
+            let generator = generator_ident(day);
+            quote!(let input = &#generator(input)?;)
+        },
+    };
+    let answer = match result_type(&function.sig.output) {
+        Some(_) => quote!(#call?),
+        None => quote!(#call),
+    };
+
+    let (name_hash, other_part) = (fnv(&name), 3 - part);
+
+    Ok(quote! {
+        #function
+
+        impl ::aoc_core::registry::Registered<#day, #part, #name_hash> for crate::AocSolutions {}
+
+        const _: () = {
+            const _: fn() = ::aoc_core::registry::assert_registered::<crate::AocSolutions, #day, #other_part, #name_hash>;
+
+            fn solve(
+                input: &str,
+                params: &::aoc_core::params::Params,
+            ) -> ::std::result::Result<::aoc_core::answer::Answer, ::aoc_core::error::Error> {
+                let _ = params;
+                #input
+                ::std::result::Result::Ok(::std::convert::Into::into(#answer))
+            }
+
+            #[::aoc_core::linkme::distributed_slice(::aoc_core::registry::PARTS)]
+            #[linkme(crate = ::aoc_core::linkme)]
+            static PART: ::aoc_core::registry::Part = ::aoc_core::registry::Part {
+                day: #day,
+                part: #part,
+                name: #name,
+                solver: &solve,
+                params: #params,
+            };
+        };
+    })
+}
+
+fn expand_generator(attributes: Attributes, function: &ItemFn) -> syn::Result<TokenStream2> {
+    let day = attributes.day.ok_or_else(|| Error::new(Span::call_site(), "missing `day = N`"))?;
+
+    let ident = &function.sig.ident;
+    let generator = generator_ident(day);
+    if function.sig.inputs.len() != 1 {
+        return Err(Error::new(function.sig.inputs.span(), "expected only the input"));
+    }
+    let (output, parsed) = match (&function.sig.output, result_type(&function.sig.output)) {
+        (_, Some(ok)) => (ok, quote!(#ident(input)?)),
+        (ReturnType::Type(_, ty), None) => (&**ty, quote!(#ident(input))),
+        (ReturnType::Default, None) => return Err(Error::new(function.sig.span(), "expected the parsed input to be returned")),
+    };
+
+    Ok(quote! {
+        #function
+
+        impl ::aoc_core::registry::Registered<#day, 0, 0> for crate::AocSolutions {}
+
+        #[doc(hidden)]
+        fn #generator(input: &str) -> ::std::result::Result<#output, ::aoc_core::error::Error> {
+            ::std::result::Result::Ok(#parsed)
+        }
+    })
+}
+
+
+/// What the parts call to parse their input. Parts find it by name, so they
+/// have to be in the generator's module or one that imports from it.
+fn generator_ident(day: u8) -> syn::Ident {
+    format_ident!("__aoc_generator_day_{}", day)
+}
+
+fn takes_raw_input(argument: &FnArg) -> bool {
+    match argument {
+        FnArg::Typed(typed) => match &*typed.ty {
+            Type::Reference(reference) => matches!(&*reference.elem, Type::Path(p) if p.path.is_ident("str")),
+            _ => false,
+        },
+        FnArg::Receiver(_) => false,
+    }
+}
+
+/// The `T` in a return type of `Result<T, E>`, or `None` for other types.
+fn result_type(output: &ReturnType) -> Option<&Type> {
+    let path = match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => &path.path,
+            _ => return None,
+        },
+        ReturnType::Default => return None,
+    };
+
+    let last = path.segments.last().filter(|s| s.ident == "Result")?;
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 64 bit FNV-1a, the same hash as `aoc_core::utils::hash`.
+fn fnv(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_takes_raw_input() {
+        let function: ItemFn = parse_quote!(fn part_1(input: &str, params: &Params) {});
+        let generated: ItemFn = parse_quote!(fn part_1(rules: &[Rule]) {});
+
+        assert!(takes_raw_input(&function.sig.inputs[0]));
+        assert!(!takes_raw_input(&generated.sig.inputs[0]));
+    }
+
+    #[test]
+    fn test_result_type() {
+        let result: ReturnType = parse_quote!(-> std::result::Result<Vec<Rule>, ParseError>);
+        let expected: Type = parse_quote!(Vec<Rule>);
+
+        assert_eq!(result_type(&result), Some(&expected));
+        assert_eq!(result_type(&parse_quote!(-> usize)), None);
+        assert_eq!(result_type(&ReturnType::Default), None);
+    }
+}
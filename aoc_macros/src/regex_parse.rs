@@ -0,0 +1,145 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use regex::Regex;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let pattern = pattern(input)?;
+    let regex = Regex::new(&pattern.value()).map_err(|e| Error::new(pattern.span(), e))?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(Error::new(Span::call_site(), "RegexParse only works on structs")),
+    };
+    let by_name = matches!(fields, Fields::Named(_)) && regex.capture_names().any(|n| n.is_some());
+    let groups = regex.captures_len() - 1;
+    if !by_name && groups != fields.len() {
+        let message = format!("the pattern has {} groups for {} fields", groups, fields.len());
+        return Err(Error::new(pattern.span(), message));
+    }
+
+    let ty = &input.ident;
+    let values = fields.iter().enumerate().map(|(i, field)| {
+        let index = i + 1;
+        let (group, description) = match &field.ident {
+            Some(ident) if by_name => {
+                let name = ident.to_string();
+                if regex.capture_names().all(|n| n != Some(name.as_str())) {
+                    return Err(Error::new(ident.span(), format!("the pattern has no group named `{}`", name)));
+                }
+                (quote!(captures.name(#name)), format!("invalid {} `{}`", ty, name))
+            },
+            Some(ident) => (quote!(captures.get(#index)), format!("invalid {} `{}`", ty, ident)),
+            None => (quote!(captures.get(#index)), format!("invalid {} field {}", ty, i)),
+        };
+
+        let value = match option_type(&field.ty) {
+            Some(inner) => quote! {
+                match #group {
+                    Some(m) => Some(m.as_str().parse::<#inner>().map_err(|_| ParseError::new(#description, s))?),
+                    None => None,
+                }
+            },
+            None => {
+                let field_ty = &field.ty;
+                quote! {
+                    #group
+                        .ok_or_else(|| ParseError::new(#description, s))?
+                        .as_str()
+                        .parse::<#field_ty>()
+                        .map_err(|_| ParseError::new(#description, s))?
+                }
+            },
+        };
+
+        Ok(match &field.ident {
+            Some(ident) => quote!(#ident: #value),
+            None => value,
+        })
+    }).collect::<syn::Result<Vec<_>>>()?;
+
+    let construct = match fields {
+        Fields::Named(_) => quote!(Self { #(#values),* }),
+        _ => quote!(Self(#(#values),*)),
+    };
+    let mismatch = format!("doesn't match the {} pattern", ty);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ty #ty_generics #where_clause {
+            type Err = ::aoc_core::error::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                use ::aoc_core::error::ParseError;
+
+                static REGEX: ::std::sync::OnceLock<::aoc_core::regex::Regex> = ::std::sync::OnceLock::new();
+                let regex = REGEX.get_or_init(|| ::aoc_core::regex::Regex::new(#pattern).unwrap());
+                let captures = regex.captures(s).ok_or_else(|| ParseError::new(#mismatch, s))?;
+
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+
+fn pattern(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut attributes = input.attrs.iter().filter(|a| a.path().is_ident("regex"));
+
+    match (attributes.next(), attributes.next()) {
+        (Some(attribute), None) => attribute.parse_args(),
+        (_, Some(extra)) => Err(Error::new(extra.span(), "only one #[regex] is allowed")),
+        (None, None) => Err(Error::new(Span::call_site(), "expected a #[regex(r\"...\")] pattern")),
+    }
+}
+
+/// The `T` in `Option<T>`, for fields whose group may not take part in a match.
+fn option_type(ty: &Type) -> Option<&Type> {
+    let last = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if last.ident != "Option" {
+        return None;
+    }
+
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_expand_errors() {
+        let error = |input: DeriveInput| expand(&input).unwrap_err().to_string();
+
+        assert_eq!(error(parse_quote! { struct A(u8); }), "expected a #[regex(r\"...\")] pattern");
+        assert!(error(parse_quote! { #[regex("(")] struct A(u8); }).contains("unclosed group"));
+        assert_eq!(error(parse_quote! { #[regex("(a)(b)")] struct A(u8); }), "the pattern has 2 groups for 1 fields");
+        assert_eq!(
+            error(parse_quote! { #[regex("(?P<a>x)")] struct A { b: u8 } }),
+            "the pattern has no group named `b`"
+        );
+        assert_eq!(error(parse_quote! { #[regex("(a)")] enum A { B } }), "RegexParse only works on structs");
+    }
+
+    #[test]
+    fn test_option_type() {
+        let inner: Type = parse_quote!(u8);
+
+        assert_eq!(option_type(&parse_quote!(Option<u8>)), Some(&inner));
+        assert_eq!(option_type(&parse_quote!(std::option::Option<u8>)), Some(&inner));
+        assert_eq!(option_type(&parse_quote!(Vec<u8>)), None);
+    }
+}
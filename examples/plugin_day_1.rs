@@ -0,0 +1,63 @@
+//! An example plugin with a brute force day 1. Build it with
+//! `cargo build --release --example plugin_day_1` and copy
+//! `target/release/examples/libplugin_day_1.so` into `plugins/`.
+
+use std::os::raw::c_char;
+use advent_of_code::plugin::{self, PluginDay, ABI_VERSION};
+
+
+fn entries(input: &str) -> Result<Vec<i64>, String> {
+    input.lines().map(|l| l.trim().parse().map_err(|_| format!("invalid entry {:?}", l))).collect()
+}
+
+fn part_1(input: &str) -> Result<String, String> {
+    let entries = entries(input)?;
+
+    for (i, a) in entries.iter().enumerate() {
+        for b in &entries[i + 1..] {
+            if a + b == 2020 {
+                return Ok((a * b).to_string());
+            }
+        }
+    }
+
+    Err("no pair sums to 2020".to_string())
+}
+
+fn part_2(input: &str) -> Result<String, String> {
+    let entries = entries(input)?;
+
+    for (i, a) in entries.iter().enumerate() {
+        for (j, b) in entries.iter().enumerate().skip(i + 1) {
+            for c in &entries[j + 1..] {
+                if a + b + c == 2020 {
+                    return Ok((a * b * c).to_string());
+                }
+            }
+        }
+    }
+
+    Err("no triple sums to 2020".to_string())
+}
+
+
+unsafe extern "C" fn solve_part_1(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+    plugin::solve(part_1, input, input_len, out, out_cap, out_len)
+}
+
+unsafe extern "C" fn solve_part_2(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+    plugin::solve(part_2, input, input_len, out, out_cap, out_len)
+}
+
+static PLUGIN: PluginDay = PluginDay {
+    abi_version: ABI_VERSION,
+    day: 1,
+    name: b"brute force\0".as_ptr() as *const c_char,
+    part_1: solve_part_1,
+    part_2: solve_part_2,
+};
+
+#[no_mangle]
+pub extern "C" fn aoc_plugin() -> *const PluginDay {
+    &PLUGIN
+}
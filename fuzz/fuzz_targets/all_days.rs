@@ -0,0 +1,16 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc_core::params::Params;
+use aoc_core::registry;
+// Only linked in for the days it registers
+use aoc_2020 as _;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        for day in registry::implementations() {
+            let params = Params::defaults(day.params);
+            let _ = (day.part_1)(s, &params);
+            let _ = (day.part_2)(s, &params);
+        }
+    }
+});
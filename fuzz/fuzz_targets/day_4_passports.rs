@@ -0,0 +1,10 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aoc_2020::day_4;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let lines: Vec<&str> = s.split("\n\n").collect();
+        let _ = day_4::get_passports(&lines);
+    }
+});
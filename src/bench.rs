@@ -0,0 +1,386 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use aoc_core::answer::Answer;
+use aoc_core::cancel::{self, Token};
+use aoc_core::error::Error;
+use aoc_core::params::Params;
+use aoc_core::registry::{Day, Solver};
+use aoc_core::utils;
+use crate::runner;
+
+
+pub const HISTORY_PATH: &str = "bench_history.tsv";
+
+
+/// Timings for one part of one day from a single benchmark run, stored as a
+/// tab separated line in the history file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub run: u32,
+    pub timestamp: u64,
+    pub revision: String,
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: u64,
+    pub samples: Vec<Duration>,
+}
+
+impl Record {
+    pub fn median(&self) -> Duration {
+        median(&self.samples)
+    }
+
+    fn to_line(&self) -> String {
+        let samples: Vec<String> = self.samples.iter().map(|s| s.as_nanos().to_string()).collect();
+
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{:016x}\t{}",
+            self.run, self.timestamp, self.revision, self.day, self.part, self.input_hash, samples.join(",")
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        match fields[..] {
+            [run, timestamp, revision, day, part, input_hash, samples] => Some(Self {
+                run: run.parse().ok()?,
+                timestamp: timestamp.parse().ok()?,
+                revision: revision.to_string(),
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                input_hash: u64::from_str_radix(input_hash, 16).ok()?,
+                samples: samples
+                    .split(',')
+                    .map(|s| s.parse().ok().map(Duration::from_nanos))
+                    .collect::<Option<_>>()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+
+/// Times both parts of a day `samples` times each, after one warm-up run. A
+/// part that fails the warm-up, by panicking, running out of time or
+/// otherwise, isn't timed.
+pub fn measure(day: &Day, input: &str, params: &Params, timeouts: &[Option<Duration>], samples: usize) -> Vec<Vec<Duration>> {
+    [day.part_1, day.part_2]
+        .iter()
+        .enumerate()
+        .map(|(i, &solver)| {
+            let timeout = timeouts.get(i).copied().flatten();
+
+            match run(solver, input, params, timeout) {
+                Ok(_) => time(solver, input, params, timeout, samples),
+                Err(_) => vec![],
+            }
+        })
+        .collect()
+}
+
+/// Times a part that's known to work `samples` times, each run within the
+/// time budget if there is one. Timing stops at the first run that fails.
+pub fn time(solver: Solver, input: &str, params: &Params, timeout: Option<Duration>, samples: usize) -> Vec<Duration> {
+    (0..samples)
+        .map_while(|_| {
+            let start = Instant::now();
+            run(solver, input, params, timeout).ok()?;
+            Some(start.elapsed())
+        })
+        .collect()
+}
+
+fn run(solver: Solver, input: &str, params: &Params, timeout: Option<Duration>) -> Result<Answer, Error> {
+    let token = timeout.map_or_else(Token::new, Token::with_budget);
+    cancel::with_token(token, || runner::solve(solver, input, params))
+}
+
+/// Benchmarks a day and tags the timings for the history file. Parts that
+/// couldn't be timed are left out.
+pub fn record(run: u32, revision: &str, day: &Day, input: &str, params: &Params, timeouts: &[Option<Duration>], samples: usize) -> Vec<Record> {
+    let timestamp = now();
+
+    measure(day, input, params, timeouts, samples)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, samples)| !samples.is_empty())
+        .map(|(i, samples)| Record {
+            run,
+            timestamp,
+            revision: revision.to_string(),
+            day: day.day,
+            part: i as u8 + 1,
+            input_hash: utils::hash(input),
+            samples,
+        })
+        .collect()
+}
+
+pub fn read_history(path: &str) -> io::Result<Vec<Record>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text.lines().filter_map(Record::from_line).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn append_history(path: &str, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    for record in records {
+        writeln!(file, "{}", record.to_line())?;
+    }
+
+    Ok(())
+}
+
+pub fn next_run(history: &[Record]) -> u32 {
+    history.iter().map(|r| r.run).max().map_or(1, |r| r + 1)
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// The checked out commit, marked dirty when there are uncommitted changes.
+pub fn git_revision() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+
+    match (git(&["rev-parse", "--short", "HEAD"]), git(&["status", "--porcelain"])) {
+        (Some(revision), Some(status)) if !status.is_empty() => format!("{}-dirty", revision),
+        (Some(revision), _) => revision,
+        (None, _) => "unknown".to_string(),
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Regression,
+    Improvement,
+    NoChange,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub candidate: Duration,
+    pub p_value: f64,
+    pub input_changed: bool,
+    pub verdict: Verdict,
+}
+
+impl Comparison {
+    /// Relative change of the median, positive when the candidate is slower.
+    pub fn change(&self) -> f64 {
+        self.candidate.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verdict = match self.verdict {
+            Verdict::Regression => "REGRESSION",
+            Verdict::Improvement => "improvement",
+            Verdict::NoChange => "",
+        };
+
+        write!(
+            f,
+            "Day {:>2} part {}  {:>12?} -> {:>12?}  {:>+7.1}%  p={:.3}  {}{}",
+            self.day, self.part, self.baseline, self.candidate, 100.0 * self.change(), self.p_value, verdict,
+            if self.input_changed { " (input changed)" } else { "" }
+        )
+    }
+}
+
+/// Compares every day and part present in both runs. A difference counts
+/// when a two-sided Mann-Whitney U test gives a p-value below `alpha`.
+pub fn compare(baseline: &[Record], candidate: &[Record], alpha: f64) -> Vec<Comparison> {
+    candidate
+        .iter()
+        .filter_map(|c| {
+            let b = baseline.iter().find(|b| b.day == c.day && b.part == c.part)?;
+            let p_value = mann_whitney(&b.samples, &c.samples);
+            let verdict = match (p_value < alpha, c.median() > b.median()) {
+                (true, true) => Verdict::Regression,
+                (true, false) => Verdict::Improvement,
+                (false, _) => Verdict::NoChange,
+            };
+
+            Some(Comparison {
+                day: c.day,
+                part: c.part,
+                baseline: b.median(),
+                candidate: c.median(),
+                p_value,
+                input_changed: b.input_hash != c.input_hash,
+                verdict,
+            })
+        })
+        .collect()
+}
+
+
+pub fn median(samples: &[Duration]) -> Duration {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+
+    match sorted.len() {
+        0 => Duration::default(),
+        n if n % 2 == 0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        n => sorted[n / 2],
+    }
+}
+
+/// Two-sided p-value of the Mann-Whitney U test, using the normal
+/// approximation with tie correction.
+fn mann_whitney(a: &[Duration], b: &[Duration]) -> f64 {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+
+    let mut all: Vec<(Duration, bool)> = a.iter().map(|&s| (s, true)).chain(b.iter().map(|&s| (s, false))).collect();
+    all.sort();
+
+    // Average ranks over runs of equal values
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let j = (i..all.len()).find(|&j| all[j].0 != all[i].0).unwrap_or(all.len());
+        let rank = (i + j + 1) as f64 / 2.0;
+        rank_sum_a += rank * all[i..j].iter().filter(|x| x.1).count() as f64;
+        tie_term += ((j - i).pow(3) - (j - i)) as f64;
+        i = j;
+    }
+
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let n = n1 + n2;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        return 1.0;
+    }
+
+    let z = (u - n1 * n2 / 2.0).abs() / variance.sqrt();
+    erfc(z / std::f64::consts::SQRT_2).min(1.0)
+}
+
+/// Complementary error function, Numerical Recipes' Chebyshev fit
+/// (relative error below 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t * (-z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+        + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+        + t * (-0.82215223 + t * 0.17087277))))))))).exp();
+
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::registry::{Source, DEFAULT};
+
+    fn record(run: u32, part: u8, micros: &[u64]) -> Record {
+        Record {
+            run,
+            timestamp: 1608000000,
+            revision: "abc1234".to_string(),
+            day: 9,
+            part,
+            input_hash: 0xfeed,
+            samples: micros.iter().map(|&m| Duration::from_micros(m)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_record_line() {
+        let r = record(3, 2, &[10, 12, 11]);
+
+        assert_eq!(r.to_line(), "3\t1608000000\tabc1234\t9\t2\t000000000000feed\t10000,12000,11000");
+        assert_eq!(Record::from_line(&r.to_line()), Some(r));
+        assert_eq!(Record::from_line("3\tnot enough fields"), None);
+    }
+
+    fn count_lines(input: &str, _: &Params) -> Result<Answer, Error> {
+        Ok(input.lines().count().into())
+    }
+
+    fn spin(_: &str, _: &Params) -> Result<Answer, Error> {
+        loop {
+            cancel::check()?;
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let day = Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &count_lines, part_2: &spin, params: &[] };
+        let timings = measure(&day, "a", &Params::default(), &[None, Some(Duration::from_millis(10))], 3);
+
+        assert_eq!(timings.iter().map(|t| t.len()).collect::<Vec<_>>(), vec![3, 0]);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(record(1, 1, &[5, 1, 3]).median(), Duration::from_micros(3));
+        assert_eq!(record(1, 1, &[4, 1, 2, 3]).median(), Duration::from_micros(2) + Duration::from_nanos(500));
+    }
+
+    #[test]
+    fn test_mann_whitney() {
+        let fast: Vec<Duration> = (100..120).map(Duration::from_micros).collect();
+        let slow: Vec<Duration> = (130..150).map(Duration::from_micros).collect();
+        let mixed: Vec<Duration> = (100..120).rev().map(Duration::from_micros).collect();
+
+        assert!(mann_whitney(&fast, &slow) < 0.001);
+        assert!(mann_whitney(&fast, &mixed) > 0.99);
+        assert_eq!(mann_whitney(&fast, &[]), 1.0);
+    }
+
+    #[test]
+    fn test_erfc() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-7);
+        assert!((erfc(1.0) - 0.157299207).abs() < 1e-7);
+        assert!((erfc(-1.0) - 1.842700793).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![record(1, 1, &[100, 101, 102, 103, 104, 105, 106, 107]), record(1, 2, &[50; 8])];
+        let candidate = vec![
+            record(2, 1, &[120, 121, 122, 123, 124, 125, 126, 127]),
+            record(2, 2, &[49, 50, 51, 50, 49, 51, 50, 50]),
+        ];
+
+        let comparisons = compare(&baseline, &candidate, 0.05);
+
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].verdict, Verdict::Regression);
+        assert!((comparisons[0].change() - 0.2).abs() < 0.01);
+        assert_eq!(comparisons[1].verdict, Verdict::NoChange);
+        assert!(!comparisons[1].input_changed);
+    }
+
+    #[test]
+    fn test_next_run() {
+        assert_eq!(next_run(&[]), 1);
+        assert_eq!(next_run(&[record(4, 1, &[1]), record(2, 1, &[1])]), 5);
+    }
+}
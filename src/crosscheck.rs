@@ -0,0 +1,167 @@
+//! Runs every implementation of a day on the same input and compares them.
+
+use std::fmt;
+use std::time::Duration;
+use aoc_core::answer::Answer;
+use aoc_core::error::Error;
+use aoc_core::params::Config;
+use aoc_core::registry::Day;
+use crate::bench;
+use crate::runner;
+
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub name: &'static str,
+    pub answer: Result<Answer, Error>,
+    pub median: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartCheck {
+    pub part: u8,
+    pub expected: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+impl PartCheck {
+    pub fn agrees(&self) -> bool {
+        self.entries.windows(2).all(|pair| pair[0].answer == pair[1].answer)
+    }
+
+    fn fastest(&self) -> Duration {
+        self.entries.iter().filter(|e| e.answer.is_ok()).map(|e| e.median).min().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Crosscheck {
+    pub day: u8,
+    pub parts: Vec<PartCheck>,
+}
+
+impl Crosscheck {
+    pub fn agrees(&self) -> bool {
+        self.parts.iter().all(|p| p.agrees())
+    }
+}
+
+impl fmt::Display for Crosscheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "======== Day {} ========", self.day)?;
+
+        for part in &self.parts {
+            let fastest = part.fastest().as_secs_f64();
+
+            for (i, entry) in part.entries.iter().enumerate() {
+                let label = if i == 0 { format!("Part {}", part.part) } else { String::new() };
+                let answer = match &entry.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(e) => format!("failed: {}", e),
+                };
+                let relative = if fastest > 0.0 { entry.median.as_secs_f64() / fastest } else { 1.0 };
+                let wrong = match (&entry.answer, &part.expected) {
+                    (Ok(answer), Some(expected)) if !answer.matches(expected) => format!(" (expected {})", expected),
+                    _ => String::new(),
+                };
+
+                writeln!(
+                    f,
+                    "{:<6}  {:<16} {:>16}  {:>10.2?}  {:>6.2}x{}",
+                    label, entry.name, answer, entry.median, relative, wrong
+                )?;
+            }
+
+            if !part.agrees() {
+                writeln!(f, "Part {}: implementations disagree", part.part)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+/// Runs each implementation of one day, then `samples` more times per part
+/// for the timings, within the configured time budgets. Parts that failed
+/// aren't timed.
+pub fn crosscheck(implementations: &[Day], input: &str, profile: &str, config: &Config, samples: usize) -> Crosscheck {
+    let day = implementations.first().map_or(0, |d| d.day);
+    let mut parts: Vec<PartCheck> = (1..=2).map(|part| PartCheck { part, expected: None, entries: vec![] }).collect();
+
+    for implementation in implementations {
+        let result = runner::run_input(implementation, config, input, profile);
+        let timeouts = runner::timeouts(implementation, config);
+        let solvers = [implementation.part_1, implementation.part_2];
+
+        for (((check, part), solver), timeout) in parts.iter_mut().zip(result.parts).zip(solvers).zip(timeouts) {
+            // The parameters parsed for the run, or it failed
+            let timings = match (&part.answer, config.params(day, implementation.params)) {
+                (Ok(_), Ok(params)) => bench::time(solver, input, &params, timeout, samples),
+                _ => vec![],
+            };
+
+            check.expected = check.expected.take().or(part.expected);
+            check.entries.push(Entry { name: implementation.name, answer: part.answer, median: bench::median(&timings) });
+        }
+    }
+
+    Crosscheck { day, parts }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::params::Params;
+    use aoc_core::registry::{Source, DEFAULT};
+    use crate::profile;
+
+    fn count_lines(input: &str, _: &Params) -> Result<Answer, Error> {
+        Ok(input.lines().count().into())
+    }
+
+    fn count_bytes(input: &str, _: &Params) -> Result<Answer, Error> {
+        Ok(input.len().into())
+    }
+
+    fn spin(_: &str, _: &Params) -> Result<Answer, Error> {
+        loop {
+            aoc_core::cancel::check()?;
+        }
+    }
+
+    #[test]
+    fn test_crosscheck() {
+        let implementations = [
+            Day { day: 20, name: DEFAULT, source: Source::Builtin, part_1: &count_lines, part_2: &count_lines, params: &[] },
+            Day { day: 20, name: "bytes", source: Source::Builtin, part_1: &count_lines, part_2: &count_bytes, params: &[] },
+        ];
+
+        let check = crosscheck(&implementations, "ab\ncd", profile::DEFAULT, &Config::default(), 3);
+
+        assert!(check.parts[0].agrees());
+        assert!(!check.parts[1].agrees());
+        assert!(!check.agrees());
+        assert_eq!(check.parts[1].entries[1].answer, Ok(Answer::Integer(5)));
+
+        let text = check.to_string();
+        assert!(text.starts_with("======== Day 20 ========\nPart 1  default"));
+        assert!(text.contains("\n        bytes "));
+        assert!(text.ends_with("Part 2: implementations disagree\n"));
+    }
+
+    #[test]
+    fn test_crosscheck_timeout() {
+        let implementations = [
+            Day { day: 20, name: DEFAULT, source: Source::Builtin, part_1: &count_lines, part_2: &spin, params: &[] },
+        ];
+        let mut config = Config::default();
+        config.set("day_20.part_2.timeout=0.01").unwrap();
+
+        let check = crosscheck(&implementations, "ab\ncd", profile::DEFAULT, &config, 3);
+
+        assert_eq!(check.parts[1].entries[0].answer, Err(Error::Timeout(Duration::from_millis(10))));
+        assert_eq!(check.parts[1].entries[0].median, Duration::default());
+    }
+}
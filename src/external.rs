@@ -0,0 +1,233 @@
+//! Solutions written in other languages, run as external commands.
+//!
+//! The command gets the input on stdin and the part, `1` or `2`, as its last
+//! argument, and prints the answer on stdout. A failing exit status is an
+//! error with whatever it wrote to stderr. The runner times the whole command,
+//! so the timings include starting the process.
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use aoc_core::answer::Answer;
+use aoc_core::cancel;
+use aoc_core::error::Error;
+use aoc_core::params::Params;
+use aoc_core::registry::{Day, Solver, Source};
+
+
+pub const DEFAULT_NAME: &str = "external";
+
+/// How often a running command is checked for cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// How long the output may stay open after the command exits, e.g. held by
+/// something it left running in the background.
+const OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+
+/// An external solver for a day, given as `DAY[:NAME]=COMMAND`. The command
+/// is split on whitespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spec {
+    pub day: u8,
+    pub name: String,
+    pub command: Vec<String>,
+}
+
+impl FromStr for Spec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, command) = s.split_once('=').ok_or_else(|| format!("expected DAY[:NAME]=COMMAND, got {:?}", s))?;
+        let (day, name) = target.split_once(':').unwrap_or((target, DEFAULT_NAME));
+        let day = day.parse().ok().filter(|d| (1..=25).contains(d)).ok_or_else(|| format!("invalid day {:?}", day))?;
+        let command: Vec<String> = command.split_whitespace().map(String::from).collect();
+
+        if name.is_empty() {
+            return Err("the name is empty".to_string());
+        }
+        if command.is_empty() {
+            return Err("the command is empty".to_string());
+        }
+
+        Ok(Self { day, name: name.to_string(), command })
+    }
+}
+
+
+/// Wraps an external command as a registry [`Day`].
+///
+/// The name and solvers are leaked, as they're needed for as long as the
+/// program runs.
+pub fn day(spec: Spec) -> Day {
+    let command: &'static [String] = Box::leak(spec.command.into_boxed_slice());
+    let solver = |part: u8| -> Solver { Box::leak(Box::new(move |input: &str, _: &Params| run(command, part, input))) };
+
+    Day {
+        day: spec.day,
+        name: Box::leak(spec.name.into_boxed_str()),
+        source: Source::External,
+        part_1: solver(1),
+        part_2: solver(2),
+        params: &[],
+    }
+}
+
+/// Runs `command` for one part. It's killed if the runner's time budget
+/// runs out or the run is cancelled, and it fails if its output is still held
+/// open a while after it exits.
+pub fn run(command: &[String], part: u8, input: &str) -> Result<Answer, Error> {
+    let external = |e: io::Error| Error::External(format!("{}: {}", command[0], e));
+    let mut child = Command::new(&command[0])
+        .args(&command[1..])
+        .arg(part.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(external)?;
+    let (mut stdin, stdout, stderr) = match (child.stdin.take(), child.stdout.take(), child.stderr.take()) {
+        (Some(stdin), Some(stdout), Some(stderr)) => (stdin, stdout, stderr),
+        _ => unreachable!("all three are piped"),
+    };
+
+    // The pipes are served on their own threads, so a command that prints
+    // before reading all of its input can't block on a full pipe. They're
+    // detached, as anything the command started may keep them open after
+    // it's killed.
+    let input = input.to_string();
+    // A command may exit without reading all of it
+    thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = read_all(stdout);
+    let errors = read_all(stderr);
+
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(external)? {
+            break status;
+        }
+        if let Err(e) = cancel::check() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(e);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    let output = wait_for(&output, deadline, &command[0])?.map_err(external)?;
+    let errors = wait_for(&errors, deadline, &command[0])?.map_err(external)?;
+
+    match (status.success(), output.trim()) {
+        (true, "") => Err(Error::External(format!("{} printed no answer", command[0]))),
+        (true, answer) => Ok(Answer::from_text(answer)),
+        (false, _) => Err(Error::External(format!("{} {}: {}", command[0], status, errors.trim()))),
+    }
+}
+
+fn read_all(mut pipe: impl Read + Send + 'static) -> Receiver<io::Result<String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut text = String::new();
+        let _ = sender.send(pipe.read_to_string(&mut text).map(|_| text));
+    });
+
+    receiver
+}
+
+/// Waits for a pipe to be read to the end, which it may never be if the
+/// command left something running that holds it open.
+fn wait_for(pipe: &Receiver<io::Result<String>>, deadline: Instant, name: &str) -> Result<io::Result<String>, Error> {
+    loop {
+        match pipe.recv_timeout(POLL_INTERVAL) {
+            Ok(text) => return Ok(text),
+            Err(RecvTimeoutError::Timeout) => cancel::check()?,
+            Err(RecvTimeoutError::Disconnected) => return Err(Error::External(format!("{}: reading its output failed", name))),
+        }
+        if Instant::now() > deadline {
+            return Err(Error::External(format!("{} exited but its output stayed open", name)));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::cancel::Token;
+
+    fn spec(s: &str) -> Result<Spec, String> {
+        s.parse()
+    }
+
+    /// `sh -c SCRIPT`, where the part becomes `$0`.
+    fn shell(script: &str) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn test_spec() {
+        assert_eq!(
+            spec("7=python3  day_7.py"),
+            Ok(Spec { day: 7, name: DEFAULT_NAME.to_string(), command: vec!["python3".to_string(), "day_7.py".to_string()] })
+        );
+        assert_eq!(spec("12:go=./day12").map(|s| (s.day, s.name)), Ok((12, "go".to_string())));
+        assert!(spec("7").is_err());
+        assert!(spec("26=./day26").is_err());
+        assert!(spec("7:=./day7").is_err());
+        assert!(spec("7= ").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_day() {
+        let day = day(Spec { day: 3, name: "sh".to_string(), command: shell("read n; echo $((n * $0))") });
+        let params = Params::default();
+
+        assert_eq!((day.day, day.name, day.source), (3, "sh", Source::External));
+        assert_eq!((day.part_1)("21\n", &params), Ok(Answer::Integer(21)));
+        assert_eq!((day.part_2)("21\n", &params), Ok(Answer::Integer(42)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_failures() {
+        assert_eq!(
+            run(&shell("echo oops >&2; exit 3"), 1, ""),
+            Err(Error::External("sh exit status: 3: oops".to_string()))
+        );
+        assert_eq!(run(&shell("true"), 1, ""), Err(Error::External("sh printed no answer".to_string())));
+        assert!(matches!(run(&["no_such_command".to_string()], 1, ""), Err(Error::External(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_large_input() {
+        // More than a pipe holds, from a command that answers before reading
+        let input = "x".repeat(1 << 20);
+        assert_eq!(run(&shell("echo 1; wc -c"), 1, &input), Ok(Answer::from_text(&format!("1\n{}", 1 << 20))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_timeout() {
+        let budget = Duration::from_millis(50);
+        let result = cancel::with_token(Token::with_budget(budget), || run(&shell("sleep 5"), 1, ""));
+
+        assert_eq!(result, Err(Error::Timeout(budget)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_output_left_open() {
+        // The background sleep keeps stdout open after the shell exits
+        let command = shell("sleep 5 & echo 1");
+        let budget = Duration::from_millis(50);
+        let result = cancel::with_token(Token::with_budget(budget), || run(&command, 1, ""));
+
+        assert_eq!(result, Err(Error::Timeout(budget)));
+        assert_eq!(run(&command, 1, ""), Err(Error::External("sh exited but its output stayed open".to_string())));
+    }
+}
@@ -1,12 +1,12 @@
-mod utils;
-extern crate regex;
-pub mod day_10;
-pub mod day_9;
-pub mod day_8;
-pub mod day_7;
-pub mod day_6;
-pub mod day_5;
-pub mod day_4;
-pub mod day_3;
-pub mod day_2;
-pub mod day_1;
+pub mod profile;
+pub mod runner;
+pub mod report;
+pub mod bench;
+pub mod crosscheck;
+pub mod external;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "tui")]
+pub mod tui;
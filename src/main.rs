@@ -1,13 +1,542 @@
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::Level;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use aoc_core::input::Input;
+use aoc_core::observe;
+use aoc_core::params::Config;
+use aoc_core::registry::{self, Day};
+// Only linked in for the days it registers
+use aoc_2020 as _;
+use advent_of_code::{bench, crosscheck, external, profile, report, runner};
+#[cfg(feature = "plugins")]
+use advent_of_code::plugin;
+#[cfg(feature = "serve")]
+use advent_of_code::serve;
+
+const USAGE: &str = "Usage: advent_of_code [-v|-vv] [--trace-file FILE] [--report FILE] [--record FILE.{txt,svg}] [--max-frames N]
+                      [--plugins DIR] [--external DAY[:NAME]=COMMAND]... [--config FILE] [--param day_N.NAME=VALUE]... [--list-params] [--mmap]
+                      [--profile NAME | --all-profiles] [--timeout SECONDS] [DAY]...
+       advent_of_code bench [--samples N] [DAY]...
+       advent_of_code bench list
+       advent_of_code bench compare BASELINE_RUN [RUN]
+       advent_of_code crosscheck [--samples N] [--profile NAME | --all-profiles] [DAY]...
+       advent_of_code tui
+       advent_of_code serve [--addr HOST:PORT] [--max-body BYTES] [--workers N] [--timeout SECONDS]";
+
+#[derive(Debug, Default, PartialEq)]
+enum Command {
+    #[default]
+    Run,
+    Bench,
+    BenchList,
+    BenchCompare(Vec<u32>),
+    Crosscheck,
+    #[cfg(feature = "tui")]
+    Tui,
+    #[cfg(feature = "serve")]
+    Serve,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    command: Command,
+    days: Vec<u8>,
+    samples: Option<usize>,
+    verbosity: usize,
+    trace_file: Option<String>,
+    report: Option<String>,
+    plugins: Option<String>,
+    externals: Vec<external::Spec>,
+    record: Option<String>,
+    max_frames: Option<usize>,
+    addr: Option<String>,
+    max_body: Option<usize>,
+    workers: Option<usize>,
+    timeout: Option<f64>,
+    config: Option<String>,
+    params: Vec<String>,
+    list_params: bool,
+    profile: Option<String>,
+    all_profiles: bool,
+    #[cfg(feature = "mmap")]
+    mmap: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--report" => parsed.report = Some(value()?),
+            "--plugins" => parsed.plugins = Some(value()?),
+            "--external" => parsed.externals.push(value()?.parse().map_err(|e| format!("Invalid external solver: {}", e))?),
+            "--record" => parsed.record = Some(value()?),
+            "--max-frames" => parsed.max_frames = Some(value()?.parse().map_err(|_| "Invalid frame count".to_string())?),
+            "--addr" => parsed.addr = Some(value()?),
+            "--max-body" => parsed.max_body = Some(value()?.parse().map_err(|_| "Invalid body size".to_string())?),
+            "--workers" => parsed.workers = Some(value()?.parse().map_err(|_| "Invalid worker count".to_string())?),
+            "--timeout" => parsed.timeout = Some(value()?.parse().map_err(|_| "Invalid timeout".to_string())?),
+            "--trace-file" => parsed.trace_file = Some(value()?),
+            "--config" => parsed.config = Some(value()?),
+            "--param" => parsed.params.push(value()?),
+            "--list-params" => parsed.list_params = true,
+            "--profile" => parsed.profile = Some(value()?),
+            "--all-profiles" => parsed.all_profiles = true,
+            #[cfg(feature = "mmap")]
+            "--mmap" => parsed.mmap = true,
+            "--samples" => parsed.samples = Some(value()?.parse().map_err(|_| "Invalid sample count".to_string())?),
+            flag if flag.len() > 1 && flag.starts_with('-') && flag[1..].chars().all(|c| c == 'v') => {
+                parsed.verbosity += flag.len() - 1
+            },
+            "bench" if parsed.command == Command::Run && parsed.days.is_empty() => parsed.command = Command::Bench,
+            #[cfg(feature = "tui")]
+            "tui" if parsed.command == Command::Run && parsed.days.is_empty() => parsed.command = Command::Tui,
+            #[cfg(feature = "serve")]
+            "serve" if parsed.command == Command::Run && parsed.days.is_empty() => parsed.command = Command::Serve,
+            "crosscheck" if parsed.command == Command::Run && parsed.days.is_empty() => parsed.command = Command::Crosscheck,
+            "list" if parsed.command == Command::Bench && parsed.days.is_empty() => parsed.command = Command::BenchList,
+            "compare" if parsed.command == Command::Bench && parsed.days.is_empty() => {
+                parsed.command = Command::BenchCompare(vec![])
+            },
+            run if matches!(parsed.command, Command::BenchCompare(_)) => match &mut parsed.command {
+                Command::BenchCompare(runs) if runs.len() < 2 => {
+                    runs.push(run.parse().map_err(|_| format!("Invalid run {:?}", run))?)
+                },
+                _ => return Err(format!("Unexpected argument {:?}", run)),
+            },
+            day => parsed.days.push(day.parse().map_err(|_| format!("Invalid day {:?}", day))?),
+        }
+    }
+
+    if parsed.command == Command::BenchCompare(vec![]) {
+        return Err("bench compare needs a baseline run".to_string());
+    }
+
+    Ok(parsed)
+}
+
+fn init_tracing(verbosity: usize, trace_file: Option<&str>, log_buffer: Option<BoxMakeWriter>) -> Result<(), String> {
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    let subscriber = |level| tracing_subscriber::fmt().with_max_level(level);
+
+    match (trace_file, log_buffer) {
+        // A trace file is read afterwards, so it gets the debug events even without -v
+        (Some(path), _) => {
+            let file = File::create(path).map_err(|e| format!("Couldn't create trace file {}: {}", path, e))?;
+            subscriber(level.max(Level::DEBUG)).with_ansi(false).with_writer(Mutex::new(file)).init()
+        },
+        (None, Some(buffer)) => subscriber(level).with_ansi(false).with_writer(buffer).init(),
+        (None, None) => subscriber(level).with_writer(std::io::stderr).init(),
+    }
+
+    Ok(())
+}
+
+fn load_config(args: &Args) -> Result<Config, String> {
+    let mut config = match &args.config {
+        Some(path) => {
+            let text = fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+            Config::parse(&text).map_err(|e| format!("{}: {}", path, e))?
+        },
+        None => Config::default(),
+    };
+
+    for assignment in &args.params {
+        config.set(assignment).map_err(|e| e.to_string())?;
+    }
+    if let Some(timeout) = args.timeout {
+        config.set_default_timeout(Duration::try_from_secs_f64(timeout).map_err(|e| e.to_string())?);
+    }
+
+    Ok(config)
+}
+
+fn list_params() {
+    for day in registry::days() {
+        for param in day.params {
+            println!("day_{}.{} = {:?}  # {}", day.day, param.name, param.default, param.description);
+        }
+    }
+}
+
+/// The built-in days, followed by the plugins' and external commands'
+/// versions of the same day.
+fn available_days(args: &Args, builtin: Vec<Day>) -> Vec<Day> {
+    let mut days = builtin;
+
+    load_plugins(args, &mut days);
+    days.extend(args.externals.iter().cloned().map(external::day));
+
+    days.sort_by_key(|d| d.day);
+    days
+}
+
+#[cfg(feature = "plugins")]
+fn load_plugins(args: &Args, days: &mut Vec<Day>) {
+    let dir = args.plugins.as_deref().unwrap_or(plugin::DEFAULT_DIR);
+
+    match plugin::load_dir(dir.as_ref()) {
+        Ok(plugins) => {
+            for (path, day) in plugins {
+                match day {
+                    Ok(day) => days.push(day),
+                    Err(e) => eprintln!("Couldn't load plugin {}: {}", path.display(), e),
+                }
+            }
+        },
+        Err(e) => eprintln!("Couldn't read {}: {}", dir, e),
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+fn load_plugins(_args: &Args, _days: &mut Vec<Day>) {}
+
+fn selected_days(args: &Args, available: Vec<Day>) -> Vec<Day> {
+    for day in &args.days {
+        if !available.iter().any(|d| d.day == *day) {
+            eprintln!("Day {} is not enabled in this build", day);
+        }
+    }
+
+    available
+        .into_iter()
+        .filter(|d| args.days.is_empty() || args.days.contains(&d.day))
+        .collect()
+}
+
+#[cfg(feature = "mmap")]
+fn read_input(args: &Args, path: &str) -> std::io::Result<Input> {
+    match args.mmap {
+        true => Input::map(path),
+        false => Input::read(path),
+    }
+}
+
+#[cfg(not(feature = "mmap"))]
+fn read_input(_args: &Args, path: &str) -> std::io::Result<Input> {
+    Input::read(path)
+}
+
+fn selected_profiles(args: &Args) -> Result<Vec<String>, String> {
+    match (&args.profile, args.all_profiles) {
+        (_, true) => profile::list().map_err(|e| format!("Couldn't list profiles in {}: {}", profile::DATA_DIR, e)),
+        (Some(name), false) => Ok(vec![name.clone()]),
+        (None, false) => Ok(vec![profile::DEFAULT.to_string()]),
+    }
+}
+
+enum Recorder {
+    Text(Rc<RefCell<observe::TextRecorder>>),
+    Svg(Rc<RefCell<observe::SvgRecorder>>),
+}
+
+impl Recorder {
+    fn new(path: &str, max_frames: usize) -> Self {
+        if path.ends_with(".svg") {
+            Self::Svg(Rc::new(RefCell::new(observe::SvgRecorder::new(max_frames, 0.2))))
+        } else {
+            Self::Text(Rc::new(RefCell::new(observe::TextRecorder::new(max_frames))))
+        }
+    }
+
+    fn observer(&self) -> Rc<RefCell<dyn observe::Observer>> {
+        match self {
+            Self::Text(recorder) => recorder.clone(),
+            Self::Svg(recorder) => recorder.clone(),
+        }
+    }
+
+    fn contents(&self) -> String {
+        match self {
+            Self::Text(recorder) => recorder.borrow().text().to_string(),
+            Self::Svg(recorder) => recorder.borrow().svg(),
+        }
+    }
+}
+
+fn run(args: &Args, config: &Config) -> Result<(), String> {
+    let mut results = Vec::new();
+    let recorder = args.record.as_deref().map(|path| Recorder::new(path, args.max_frames.unwrap_or(1000)));
+    let profiles = selected_profiles(args)?;
+    let days = selected_days(args, available_days(args, registry::days()));
+
+    for profile in &profiles {
+        if profile != profile::DEFAULT {
+            println!("######## Profile {} ########", profile);
+        }
+
+        for day in &days {
+            let path = profile::input_path(profile, day.day);
+            let result = read_input(args, &path).map(|input| match &recorder {
+                Some(recorder) => observe::with_observer(recorder.observer(), || runner::run_input(day, config, &input, profile)),
+                None => runner::run_input(day, config, &input, profile),
+            });
+
+            match result {
+                Ok(result) => {
+                    print!("{}", result);
+                    results.push(result);
+                },
+                Err(e) => eprintln!("Day {}: couldn't read {}: {}", day.day, path, e),
+            }
+        }
+    }
+
+    if profiles.len() > 1 {
+        print!("\n{}", profile::matrix(&profiles, &results));
+    }
+
+    if let Some(path) = &args.report {
+        fs::write(path, report::html(&results)).map_err(|e| format!("Couldn't write report {}: {}", path, e))?;
+        println!("Report written to {}", path);
+    }
+
+    if let (Some(path), Some(recorder)) = (&args.record, recorder) {
+        fs::write(path, recorder.contents()).map_err(|e| format!("Couldn't write recording {}: {}", path, e))?;
+        println!("Recording written to {}", path);
+    }
+
+    Ok(())
+}
+
+fn run_bench(args: &Args, config: &Config) -> Result<(), String> {
+    let history = bench::read_history(bench::HISTORY_PATH).map_err(|e| e.to_string())?;
+    let run = bench::next_run(&history);
+    // Runs with either hasher can then be compared with `bench compare`
+    let revision = match cfg!(feature = "fast-hash") {
+        true => format!("{}+fast-hash", bench::git_revision()),
+        false => bench::git_revision(),
+    };
+    let mut records = Vec::new();
+
+    for day in selected_days(args, registry::days()) {
+        let input = match fs::read_to_string(runner::input_path(day.day)) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {}: couldn't read {}: {}", day.day, runner::input_path(day.day), e);
+                continue;
+            },
+        };
+        let params = config.params(day.day, day.params).map_err(|e| e.to_string())?;
+
+        for record in bench::record(run, &revision, &day, &input, &params, &runner::timeouts(&day, config), args.samples.unwrap_or(20)) {
+            println!("Day {:>2} part {}  median {:?}", record.day, record.part, record.median());
+            records.push(record);
+        }
+    }
+
+    bench::append_history(bench::HISTORY_PATH, &records).map_err(|e| e.to_string())?;
+    println!("Recorded run {} ({}) in {}", run, revision, bench::HISTORY_PATH);
+    Ok(())
+}
+
+fn crosscheck(args: &Args, config: &Config) -> Result<(), String> {
+    let implementations = selected_days(args, available_days(args, registry::implementations()));
+    let mut disagreements = 0;
+
+    for profile in &selected_profiles(args)? {
+        if profile != profile::DEFAULT {
+            println!("######## Profile {} ########", profile);
+        }
+
+        for (day, group) in group_by_day(&implementations) {
+            let path = profile::input_path(profile, day);
+            let input = match fs::read_to_string(&path) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Day {}: couldn't read {}: {}", day, path, e);
+                    continue;
+                },
+            };
+
+            let check = crosscheck::crosscheck(group, &input, profile, config, args.samples.unwrap_or(5));
+            print!("{}", check);
+            disagreements += check.parts.iter().filter(|p| !p.agrees()).count();
+        }
+    }
+
+    match disagreements {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) with disagreeing implementations", n)),
+    }
+}
+
+fn group_by_day(days: &[Day]) -> Vec<(u8, &[Day])> {
+    let mut groups = Vec::new();
+    let mut rest = days;
+
+    while let Some(first) = rest.first() {
+        let n = rest.iter().take_while(|d| d.day == first.day).count();
+        groups.push((first.day, &rest[..n]));
+        rest = &rest[n..];
+    }
+
+    groups
+}
+
+fn bench_list() -> Result<(), String> {
+    let history = bench::read_history(bench::HISTORY_PATH).map_err(|e| e.to_string())?;
+    let mut runs: Vec<&bench::Record> = history.iter().collect();
+    runs.dedup_by_key(|r| r.run);
+
+    for r in runs {
+        let days = history.iter().filter(|h| h.run == r.run && h.part == 1).count();
+        println!("Run {:>3}  {}  revision {}  {} days", r.run, r.timestamp, r.revision, days);
+    }
+
+    Ok(())
+}
+
+fn bench_compare(runs: &[u32]) -> Result<(), String> {
+    let history = bench::read_history(bench::HISTORY_PATH).map_err(|e| e.to_string())?;
+    let latest = bench::next_run(&history) - 1;
+    let (baseline, candidate) = (runs[0], runs.get(1).copied().unwrap_or(latest));
+    let records = |run: u32| -> Result<Vec<bench::Record>, String> {
+        let records: Vec<_> = history.iter().filter(|r| r.run == run).cloned().collect();
+        if records.is_empty() { Err(format!("No run {} in {}", run, bench::HISTORY_PATH)) } else { Ok(records) }
+    };
+
+    let comparisons = bench::compare(&records(baseline)?, &records(candidate)?, 0.05);
+    println!("Run {} against baseline run {}", candidate, baseline);
+    for comparison in &comparisons {
+        println!("{}", comparison);
+    }
+
+    let regressions = comparisons.iter().filter(|c| c.verdict == bench::Verdict::Regression).count();
+    if regressions > 0 {
+        return Err(format!("{} significant regression(s)", regressions));
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "serve")]
+fn serve(args: &Args, config: &Config) -> Result<(), String> {
+    // --timeout is already the config's default budget
+    let defaults = serve::Limits::default();
+    let limits = serve::Limits {
+        max_body: args.max_body.unwrap_or(defaults.max_body),
+        workers: args.workers.unwrap_or(defaults.workers),
+        ..defaults
+    };
+    let days = available_days(args, registry::days());
+
+    serve::serve(args.addr.as_deref().unwrap_or("127.0.0.1:2020"), days, config.clone(), limits).map_err(|e| e.to_string())
+}
 
 fn main() {
-    advent_of_code::day_1::main();
-    advent_of_code::day_2::main();
-    advent_of_code::day_3::main();
-    advent_of_code::day_4::main();
-    advent_of_code::day_5::main();
-    advent_of_code::day_6::main();
-    advent_of_code::day_7::main();
-    advent_of_code::day_8::main();
-    advent_of_code::day_9::main();
-    advent_of_code::day_10::main();
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        std::process::exit(2);
+    });
+
+    #[cfg(feature = "tui")]
+    let logs = advent_of_code::tui::LogBuffer::default();
+
+    let tracing = match args.command {
+        // The dashboard shows the log itself, and at least the debug events
+        #[cfg(feature = "tui")]
+        Command::Tui => init_tracing(args.verbosity.max(1), args.trace_file.as_deref(), Some(BoxMakeWriter::new(logs.clone()))),
+        _ => init_tracing(args.verbosity, args.trace_file.as_deref(), None),
+    };
+    if let Err(e) = tracing {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+
+    if args.list_params {
+        return list_params();
+    }
+
+    let config = load_config(&args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    });
+
+    let result = match args.command {
+        Command::Run => run(&args, &config),
+        Command::Bench => run_bench(&args, &config),
+        Command::BenchList => bench_list(),
+        Command::Crosscheck => crosscheck(&args, &config),
+        Command::BenchCompare(ref runs) => bench_compare(runs),
+        #[cfg(feature = "tui")]
+        Command::Tui => advent_of_code::tui::run(selected_days(&args, registry::days()), config, logs).map_err(|e| e.to_string()),
+        #[cfg(feature = "serve")]
+        Command::Serve => serve(&args, &config),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+        assert_eq!(
+            parse(&["-vv", "--trace-file", "trace.log", "7", "--report", "out.html", "9"]),
+            Ok(Args {
+                days: vec![7, 9],
+                verbosity: 2,
+                trace_file: Some("trace.log".to_string()),
+                report: Some("out.html".to_string()),
+                ..Args::default()
+            })
+        );
+        assert_eq!(parse(&["-v", "-v"]).map(|a| a.verbosity), Ok(2));
+        assert_eq!(
+            parse(&["--param", "day_1.target=10", "--param", "day_9.preamble=5"]).map(|a| a.params),
+            Ok(vec!["day_1.target=10".to_string(), "day_9.preamble=5".to_string()])
+        );
+        assert_eq!(
+            parse(&["--record", "day_8.svg", "--max-frames", "50", "8"]).map(|a| (a.record, a.max_frames)),
+            Ok((Some("day_8.svg".to_string()), Some(50)))
+        );
+        assert_eq!(parse(&["--plugins", "extra", "1"]).map(|a| a.plugins), Ok(Some("extra".to_string())));
+        assert_eq!(
+            parse(&["--external", "7:py=python3 day_7.py", "crosscheck"]).map(|a| (a.externals.len(), a.command)),
+            Ok((1, Command::Crosscheck))
+        );
+        assert!(parse(&["--external", "7"]).is_err());
+        #[cfg(feature = "serve")]
+        assert_eq!(
+            parse(&["serve", "--addr", "0.0.0.0:80", "--workers", "2", "--timeout", "2.5"]).map(|a| (a.command, a.addr, a.workers, a.timeout)),
+            Ok((Command::Serve, Some("0.0.0.0:80".to_string()), Some(2), Some(2.5)))
+        );
+        #[cfg(feature = "mmap")]
+        assert_eq!(parse(&["--mmap", "9"]).map(|a| (a.mmap, a.days)), Ok((true, vec![9])));
+        assert_eq!(parse(&["crosscheck", "1", "7"]).map(|a| (a.command, a.days)), Ok((Command::Crosscheck, vec![1, 7])));
+        assert_eq!(
+            parse(&["--profile", "alice", "--all-profiles"]).map(|a| (a.profile, a.all_profiles)),
+            Ok((Some("alice".to_string()), true))
+        );
+        assert!(parse(&["--report"]).is_err());
+        assert_eq!(parse(&["bench", "--samples", "5", "9"]).map(|a| (a.command, a.samples, a.days)), Ok((Command::Bench, Some(5), vec![9])));
+        assert_eq!(parse(&["bench", "list"]).map(|a| a.command), Ok(Command::BenchList));
+        assert_eq!(parse(&["bench", "compare", "3"]).map(|a| a.command), Ok(Command::BenchCompare(vec![3])));
+        assert_eq!(parse(&["bench", "compare", "3", "5"]).map(|a| a.command), Ok(Command::BenchCompare(vec![3, 5])));
+        assert!(parse(&["bench", "compare"]).is_err());
+        assert!(parse(&["bench", "compare", "1", "2", "3"]).is_err());
+        assert!(parse(&["-x"]).is_err());
+    }
 }
@@ -0,0 +1,239 @@
+//! Solutions compiled outside this crate, loaded from shared libraries.
+//!
+//! A plugin exports `aoc_plugin`, a C function returning a pointer to a static
+//! [`PluginDay`]. Its first field is the ABI version, which is checked before
+//! anything else is read so later versions can change the rest of the layout.
+//! See `examples/plugin_day_1.rs` for a plugin written in Rust.
+
+use std::ffi::CStr;
+use std::fs;
+use std::io;
+use std::os::raw::c_char;
+use std::panic;
+use std::path::{Path, PathBuf};
+use libloading::Library;
+use aoc_core::answer::Answer;
+use aoc_core::error::Error;
+use aoc_core::params::Params;
+use aoc_core::registry::{Day, Solver, Source};
+use crate::runner;
+
+
+pub const ABI_VERSION: u32 = 1;
+pub const ENTRY_SYMBOL: &[u8] = b"aoc_plugin";
+pub const DEFAULT_DIR: &str = "plugins";
+
+pub const OK: i32 = 0;
+pub const FAILED: i32 = 1;
+pub const BUFFER_TOO_SMALL: i32 = 2;
+
+/// Solves one part from `input_len` bytes of UTF-8 input.
+///
+/// The answer, or an error message when returning `FAILED`, goes in `out` and
+/// its length in `out_len`. When it's longer than `out_cap`, the solver sets
+/// `out_len` to the length needed and returns `BUFFER_TOO_SMALL`, and is then
+/// called again with a big enough buffer.
+pub type PluginSolver =
+    unsafe extern "C" fn(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32;
+
+pub type PluginEntry = unsafe extern "C" fn() -> *const PluginDay;
+
+#[repr(C)]
+pub struct PluginDay {
+    pub abi_version: u32,
+    pub day: u8,
+    /// NUL terminated, shown next to the day in the results.
+    pub name: *const c_char,
+    pub part_1: PluginSolver,
+    pub part_2: PluginSolver,
+}
+
+// Plugins hand out a pointer to static, immutable data
+unsafe impl Sync for PluginDay {}
+
+
+/// Implements a [`PluginSolver`] with a Rust function, for plugins written in
+/// Rust. A panic in `solver` fails the part, as it can't unwind into the
+/// runner.
+///
+/// # Safety
+///
+/// The arguments must be the ones passed to the [`PluginSolver`].
+pub unsafe fn solve(
+    solver: fn(&str) -> Result<String, String>,
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let result = match std::str::from_utf8(std::slice::from_raw_parts(input, input_len)) {
+        Ok(input) => panic::catch_unwind(|| solver(input))
+            .unwrap_or_else(|payload| Err(format!("panicked: {}", runner::panic_message(&*payload)))),
+        Err(e) => Err(e.to_string()),
+    };
+
+    write_answer(result, out, out_cap, out_len)
+}
+
+unsafe fn write_answer(result: Result<String, String>, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+    let (text, status) = match &result {
+        Ok(answer) => (answer, OK),
+        Err(message) => (message, FAILED),
+    };
+
+    *out_len = text.len();
+    if text.len() > out_cap {
+        return BUFFER_TOO_SMALL;
+    }
+
+    std::ptr::copy_nonoverlapping(text.as_ptr(), out, text.len());
+    status
+}
+
+fn call(solver: PluginSolver, input: &str) -> Result<Answer, Error> {
+    let mut out = vec![0; 4096];
+
+    loop {
+        let mut len = 0;
+        let status = unsafe { solver(input.as_ptr(), input.len(), out.as_mut_ptr(), out.len(), &mut len) };
+
+        if status == BUFFER_TOO_SMALL && len > out.len() {
+            out.resize(len, 0);
+            continue;
+        }
+
+        let text = match out.get(..len) {
+            Some(text) => String::from_utf8_lossy(text).into_owned(),
+            None => return Err(Error::Plugin(format!("answer length {} is out of bounds", len))),
+        };
+
+        return match status {
+            OK => Ok(Answer::from_text(&text)),
+            FAILED => Err(Error::Plugin(text)),
+            status => Err(Error::Plugin(format!("unknown status {}", status))),
+        };
+    }
+}
+
+
+/// Wraps a plugin's solvers as a registry [`Day`].
+///
+/// The solvers are leaked, as they're needed for as long as the program runs.
+pub fn day(plugin: &'static PluginDay) -> Result<Day, String> {
+    if plugin.abi_version != ABI_VERSION {
+        return Err(format!("ABI version {} is not supported, expected {}", plugin.abi_version, ABI_VERSION));
+    }
+    if !(1..=25).contains(&plugin.day) {
+        return Err(format!("invalid day {}", plugin.day));
+    }
+
+    let name = match plugin.name.is_null() {
+        true => "plugin",
+        false => unsafe { CStr::from_ptr(plugin.name) }.to_str().map_err(|_| "name is not UTF-8".to_string())?,
+    };
+    let solver = |part: PluginSolver| -> Solver { Box::leak(Box::new(move |input: &str, _: &Params| call(part, input))) };
+
+    Ok(Day {
+        day: plugin.day,
+        name,
+        source: Source::Plugin,
+        part_1: solver(plugin.part_1),
+        part_2: solver(plugin.part_2),
+        params: &[],
+    })
+}
+
+pub fn load(path: &Path) -> Result<Day, String> {
+    let library = unsafe { Library::new(path) }.map_err(|e| e.to_string())?;
+    let plugin = unsafe {
+        let entry = library.get::<PluginEntry>(ENTRY_SYMBOL).map_err(|e| e.to_string())?;
+        entry()
+    };
+    if plugin.is_null() {
+        return Err("aoc_plugin returned null".to_string());
+    }
+
+    let day = day(unsafe { &*plugin })?;
+
+    // The day points into the library, so it must never be unloaded
+    std::mem::forget(library);
+    Ok(day)
+}
+
+/// Loads every shared library in `dir`. A missing directory has no plugins.
+pub fn load_dir(dir: &Path) -> io::Result<Vec<(PathBuf, Result<Day, String>)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == std::env::consts::DLL_EXTENSION))
+        .collect();
+    paths.sort();
+
+    Ok(paths.into_iter().map(|p| {
+        let day = load(&p);
+        (p, day)
+    }).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe extern "C" fn count_bytes(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+        solve(|input| Ok(input.len().to_string()), input, input_len, out, out_cap, out_len)
+    }
+
+    unsafe extern "C" fn long_error(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+        solve(|_| Err("x".repeat(5000)), input, input_len, out, out_cap, out_len)
+    }
+
+    unsafe extern "C" fn explode(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize, out_len: *mut usize) -> i32 {
+        solve(|_| panic!("index out of bounds"), input, input_len, out, out_cap, out_len)
+    }
+
+    static PLUGIN: PluginDay = PluginDay {
+        abi_version: ABI_VERSION,
+        day: 3,
+        name: b"test\0".as_ptr() as *const c_char,
+        part_1: count_bytes,
+        part_2: long_error,
+    };
+
+    static FUTURE_PLUGIN: PluginDay = PluginDay { abi_version: ABI_VERSION + 1, ..PLUGIN };
+
+    static PANICKING_PLUGIN: PluginDay = PluginDay { part_1: explode, ..PLUGIN };
+
+    #[test]
+    fn test_day() {
+        let day = day(&PLUGIN).unwrap();
+
+        assert_eq!((day.day, day.name), (3, "test"));
+        assert_eq!((day.part_1)("abc\n", &Params::default()), Ok(Answer::Integer(4)));
+        assert_eq!((day.part_2)("", &Params::default()), Err(Error::Plugin("x".repeat(5000))));
+    }
+
+    #[test]
+    fn test_panic() {
+        let day = day(&PANICKING_PLUGIN).unwrap();
+
+        assert_eq!((day.part_1)("", &Params::default()), Err(Error::Plugin("panicked: index out of bounds".to_string())));
+    }
+
+    #[test]
+    fn test_day_version() {
+        assert!(super::day(&FUTURE_PLUGIN).is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        assert!(load(Path::new("data/day_1.txt")).is_err());
+        assert!(load_dir(Path::new("no_such_dir")).unwrap().is_empty());
+    }
+}
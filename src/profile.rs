@@ -0,0 +1,159 @@
+//! Named sets of puzzle inputs, so solutions can be compared across everyone's
+//! inputs. The default profile is `data/day_N.txt`, a profile named `alice`
+//! is `data/alice/day_N.txt`, each with its own `.answers` files.
+
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use aoc_core::registry::DEFAULT as DEFAULT_IMPLEMENTATION;
+use crate::runner::{DayResult, Status};
+
+
+pub const DEFAULT: &str = "default";
+pub const DATA_DIR: &str = "data";
+
+
+pub fn dir(profile: &str) -> String {
+    match profile {
+        DEFAULT => DATA_DIR.to_string(),
+        profile => format!("{}/{}", DATA_DIR, profile),
+    }
+}
+
+pub fn input_path(profile: &str, day: u8) -> String {
+    format!("{}/day_{}.txt", dir(profile), day)
+}
+
+pub fn answers_path(profile: &str, day: u8) -> String {
+    format!("{}/day_{}.answers", dir(profile), day)
+}
+
+/// The default profile followed by every subdirectory of `data/`, by name.
+pub fn list() -> io::Result<Vec<String>> {
+    let mut profiles = Vec::new();
+
+    for entry in fs::read_dir(DATA_DIR)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            profiles.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    profiles.sort();
+    profiles.insert(0, DEFAULT.to_string());
+
+    Ok(profiles)
+}
+
+
+/// A table with one row per day and part and one column per profile, showing
+/// each answer marked `*` if it's right, `x` if it's wrong or failed and `?`
+/// if the answer isn't known.
+pub fn matrix(profiles: &[String], results: &[DayResult]) -> String {
+    let mut rows: Vec<(u8, &str, u8)> = results
+        .iter()
+        .flat_map(|r| r.parts.iter().map(move |p| (r.day, r.name, p.part)))
+        .collect();
+    rows.sort();
+    rows.dedup();
+
+    let cell = |profile: &str, (day, name, part): (u8, &str, u8)| {
+        let result = results.iter().find(|r| r.profile == profile && r.day == day && r.name == name);
+        match result.and_then(|r| r.parts.iter().find(|p| p.part == part)) {
+            Some(part) => {
+                let mark = match part.status() {
+                    Status::Pass => '*',
+                    Status::Fail => 'x',
+                    Status::Unknown => '?',
+                };
+                match &part.answer {
+                    Ok(answer) => format!("{} {}", answer, mark),
+                    Err(_) => format!("error {}", mark),
+                }
+            },
+            None => "-".to_string(),
+        }
+    };
+    let label = |(day, name, part): (u8, &str, u8)| match name {
+        DEFAULT_IMPLEMENTATION => format!("Day {} part {}", day, part),
+        name => format!("Day {} ({}) part {}", day, name, part),
+    };
+
+    let label_width = rows.iter().map(|&row| label(row).len()).max().unwrap_or(0);
+    let widths: Vec<usize> = profiles
+        .iter()
+        .map(|profile| rows.iter().map(|&row| cell(profile, row).chars().count()).chain([profile.len()]).max().unwrap_or(0))
+        .collect();
+
+    let mut out = format!("{:<1$}", "", label_width);
+    for (profile, width) in profiles.iter().zip(&widths) {
+        let _ = write!(out, "  {:>1$}", profile, width);
+    }
+    out.push('\n');
+
+    for &row in &rows {
+        let _ = write!(out, "{:<1$}", label(row), label_width);
+        for (profile, width) in profiles.iter().zip(&widths) {
+            let _ = write!(out, "  {:>1$}", cell(profile, row), width);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use aoc_core::answer::Answer;
+    use aoc_core::error::Error;
+    use aoc_core::registry::Source;
+    use crate::runner::PartResult;
+
+    fn result(profile: &str, day: u8, answers: [Result<&str, Error>; 2], expected: [Option<&str>; 2]) -> DayResult {
+        DayResult {
+            day,
+            name: DEFAULT_IMPLEMENTATION,
+            source: Source::Builtin,
+            profile: profile.to_string(),
+            input_hash: 0,
+            parts: answers
+                .iter()
+                .zip(&expected)
+                .enumerate()
+                .map(|(i, (answer, expected))| PartResult {
+                    part: i as u8 + 1,
+                    answer: answer.clone().map(Answer::from_text),
+                    expected: expected.map(|e| e.to_string()),
+                    elapsed: Duration::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(input_path(DEFAULT, 3), "data/day_3.txt");
+        assert_eq!(answers_path("alice", 10), "data/alice/day_10.answers");
+    }
+
+    #[test]
+    fn test_matrix() {
+        let profiles = vec![DEFAULT.to_string(), "alice".to_string()];
+        let results = vec![
+            result(DEFAULT, 1, [Ok("514579"), Ok("241861950")], [Some("514579"), Some("1")]),
+            result("alice", 1, [Ok("7"), Err(Error::NoSolution)], [None, None]),
+            result(DEFAULT, 2, [Ok("2"), Ok("1")], [Some("2"), Some("1")]),
+        ];
+
+        assert_eq!(
+            matrix(&profiles, &results),
+            "                  default    alice\n\
+             Day 1 part 1     514579 *      7 ?\n\
+             Day 1 part 2  241861950 x  error x\n\
+             Day 2 part 1          2 *        -\n\
+             Day 2 part 2          1 *        -\n"
+        );
+    }
+}
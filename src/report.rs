@@ -0,0 +1,189 @@
+use std::fmt::Write;
+use std::time::Duration;
+use aoc_core::registry::{Source, DEFAULT};
+use crate::profile;
+use crate::runner::{DayResult, PartResult, Status};
+
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.4em 0.8em; border-bottom: 1px solid #ddd; text-align: left; vertical-align: top; }
+.pass { color: #2a7d2a; }
+.fail { color: #b22; }
+.unknown { color: #888; }
+.timing { width: 20em; }
+.bar { height: 0.6em; background: #4a7ebb; margin: 0.2em 0; }
+code, pre { font-size: 0.9em; }
+pre { display: inline-block; margin: 0; }
+";
+
+
+/// Renders a self-contained HTML page with one row per day.
+pub fn html(results: &[DayResult]) -> String {
+    let slowest = results
+        .iter()
+        .flat_map(|r| r.parts.iter().map(|p| p.elapsed))
+        .max()
+        .unwrap_or_default();
+    let parts: Vec<&PartResult> = results.iter().flat_map(|r| &r.parts).collect();
+    let passed = parts.iter().filter(|p| p.status() == Status::Pass).count();
+    let total: Duration = results.iter().map(|r| r.elapsed()).sum();
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Advent of Code 2020</title>\n");
+    let _ = writeln!(out, "<style>{}</style>", STYLE);
+    out.push_str("</head>\n<body>\n<h1>Advent of Code 2020</h1>\n");
+    let _ = writeln!(
+        out,
+        "<p>{} of {} parts pass, total time {}</p>",
+        passed, parts.len(), format_duration(total)
+    );
+    out.push_str("<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time</th><th>Source</th><th>Input</th></tr>\n");
+
+    for result in results {
+        out.push_str("<tr>");
+        let _ = write!(out, "<td>{}</td>", result.day);
+
+        for part in &result.parts {
+            out.push_str(&answer_cell(part));
+        }
+
+        out.push_str("<td class=\"timing\">");
+        for part in &result.parts {
+            let width = if slowest.as_nanos() == 0 {
+                0.0
+            } else {
+                100.0 * part.elapsed.as_secs_f64() / slowest.as_secs_f64()
+            };
+            let _ = write!(
+                out,
+                "<div class=\"bar\" style=\"width: {:.1}%\" title=\"Part {}\"></div>{}",
+                width, part.part, format_duration(part.elapsed)
+            );
+        }
+        out.push_str("</td>");
+
+        let source = match (result.source, result.name) {
+            (Source::Builtin, DEFAULT) => format!("<a href=\"aoc_2020/src/day_{0}.rs\">day_{0}.rs</a>", result.day),
+            (Source::Builtin, name) => format!("<a href=\"aoc_2020/src/day_{0}.rs\">day_{0}.rs</a> {1}", result.day, escape(name)),
+            (Source::Plugin, name) => format!("plugin {}", escape(name)),
+            (Source::External, name) => format!("external {}", escape(name)),
+        };
+        let _ = write!(out, "<td>{}</td>", source);
+        let _ = write!(
+            out,
+            "<td><a href=\"{}\"><code>{:016x}</code></a></td>",
+            profile::input_path(&result.profile, result.day), result.input_hash
+        );
+        out.push_str("</tr>\n");
+    }
+
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+fn answer_cell(part: &PartResult) -> String {
+    let (class, mark) = match part.status() {
+        Status::Pass => ("pass", "&#10003;"),
+        Status::Fail => ("fail", "&#10007;"),
+        Status::Unknown => ("unknown", "?"),
+    };
+    let text = match (&part.answer, &part.expected) {
+        (Ok(answer), Some(expected)) if !answer.matches(expected) => {
+            format!("{} (expected {})", escape(&answer.to_string()), escape(expected))
+        },
+        (Ok(answer), _) => escape(&answer.to_string()),
+        (Err(e), _) => escape(&e.to_string()),
+    };
+
+    // Letter-art grids need their line breaks
+    match text.contains('\n') {
+        true => format!("<td class=\"{}\">{} <pre>{}</pre></td>", class, mark, text),
+        false => format!("<td class=\"{}\">{} <code>{}</code></td>", class, mark, text),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1e6;
+
+    if micros < 1000.0 {
+        format!("{:.0} &micro;s", micros)
+    } else {
+        format!("{:.2} ms", micros / 1000.0)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut out, c| {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+        out
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answer::Answer;
+    use aoc_core::error::Error;
+
+    fn part(part: u8, answer: Result<Answer, Error>, expected: Option<&str>, micros: u64) -> PartResult {
+        PartResult {
+            part,
+            answer,
+            expected: expected.map(|e| e.to_string()),
+            elapsed: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn test_html() {
+        let results = vec![
+            DayResult {
+                day: 7,
+                name: DEFAULT,
+                source: Source::Builtin,
+                profile: profile::DEFAULT.to_string(),
+                input_hash: 0xabc,
+                parts: vec![
+                    part(1, Ok(185.into()), Some("185"), 500),
+                    part(2, Ok("<b>".into()), Some("89084"), 2000),
+                ],
+            },
+            DayResult {
+                day: 9,
+                name: "fast",
+                source: Source::Plugin,
+                profile: "alice".to_string(),
+                input_hash: 1,
+                parts: vec![
+                    part(1, Err(Error::NoSolution), None, 0),
+                    part(2, Ok("#.\n.#".into()), None, 1000),
+                ],
+            },
+        ];
+
+        let page = html(&results);
+
+        assert!(page.contains("<p>1 of 4 parts pass, total time 3.50 ms</p>"));
+        assert!(page.contains("<td class=\"pass\">&#10003; <code>185</code></td>"));
+        assert!(page.contains("<code>&lt;b&gt; (expected 89084)</code>"));
+        assert!(page.contains("<code>no solution found</code>"));
+        assert!(page.contains("<pre>#.\n.#</pre>"));
+        assert!(page.contains("style=\"width: 100.0%\""));
+        assert!(page.contains("style=\"width: 25.0%\""));
+        assert!(page.contains("<a href=\"aoc_2020/src/day_7.rs\">day_7.rs</a>"));
+        assert!(page.contains("<td>plugin fast</td>"));
+        assert!(page.contains("<a href=\"data/alice/day_9.txt\">"));
+        assert!(page.contains("<a href=\"data/day_7.txt\"><code>0000000000000abc</code></a>"));
+        assert_eq!(page.matches("<tr>").count(), 3);
+    }
+}
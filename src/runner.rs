@@ -0,0 +1,266 @@
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use aoc_core::answer::Answer;
+use aoc_core::cancel::{self, Token};
+use aoc_core::error::Error;
+use aoc_core::input::Input;
+use aoc_core::params::{Config, Params};
+use aoc_core::registry::{Day, Solver, Source, DEFAULT};
+use aoc_core::utils;
+use crate::profile;
+use tracing::{info, info_span};
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Result<Answer, Error>,
+    pub expected: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl PartResult {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Fail,
+            (Ok(answer), Some(expected)) if answer.matches(expected) => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+            (Ok(_), None) => Status::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u8,
+    pub name: &'static str,
+    pub source: Source,
+    pub profile: String,
+    pub input_hash: u64,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    pub fn elapsed(&self) -> Duration {
+        self.parts.iter().map(|p| p.elapsed).sum()
+    }
+}
+
+impl fmt::Display for DayResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            DEFAULT => writeln!(f, "======== Day {} ========", self.day)?,
+            name => writeln!(f, "======== Day {} ({}) ========", self.day, name)?,
+        }
+
+        for part in &self.parts {
+            match (&part.answer, part.status(), &part.expected) {
+                (Ok(answer), Status::Fail, Some(expected)) => {
+                    writeln!(f, "Part {} = {} (expected {})", part.part, answer, expected)?
+                },
+                (Ok(answer), _, _) if answer.is_multiline() => writeln!(f, "Part {} =\n{}", part.part, answer)?,
+                (Ok(answer), _, _) => writeln!(f, "Part {} = {}", part.part, answer)?,
+                (Err(e), _, _) => writeln!(f, "Part {} failed: {}", part.part, e)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+pub fn input_path(day: u8) -> String {
+    profile::input_path(profile::DEFAULT, day)
+}
+
+pub fn answers_path(day: u8) -> String {
+    profile::answers_path(profile::DEFAULT, day)
+}
+
+/// Known answers, one line per part. A missing file means nothing is known yet.
+pub fn read_answers(path: &str) -> Vec<Option<String>> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(|l| Some(l.trim().to_string()).filter(|a| !a.is_empty()))
+        .collect()
+}
+
+/// Calls a solver, turning a panic into [`Error::Panic`] so one broken part
+/// doesn't end the whole run.
+pub fn solve(solver: Solver, input: &str, params: &Params) -> Result<Answer, Error> {
+    panic::catch_unwind(AssertUnwindSafe(|| solver(input, params))).unwrap_or_else(|payload| Err(Error::Panic(panic_message(&*payload))))
+}
+
+/// The message a panic was started with.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        (None, None) => "unknown cause".to_string(),
+    }
+}
+
+/// Runs both parts, each within its time budget if it has one. A part that
+/// goes over fails with [`Error::Timeout`], whether or not the solver checks
+/// for cancellation; the ones that do stop when their time is up.
+pub fn run_day(day: &Day, input: &str, params: &Params, expected: &[Option<String>], timeouts: &[Option<Duration>]) -> DayResult {
+    let parts = [day.part_1, day.part_2]
+        .iter()
+        .enumerate()
+        .map(|(i, &solver)| {
+            let _span = info_span!("part", day = day.day, part = i + 1).entered();
+            let timeout = timeouts.get(i).copied().flatten();
+            let token = timeout.map_or_else(Token::new, Token::with_budget);
+
+            let start = Instant::now();
+            let answer = cancel::with_token(token, || solve(solver, input, params));
+            let elapsed = start.elapsed();
+            let answer = match timeout {
+                Some(budget) if elapsed > budget => Err(Error::Timeout(budget)),
+                _ => answer,
+            };
+            info!(?answer, ?elapsed, "solved");
+
+            PartResult {
+                part: i as u8 + 1,
+                answer,
+                expected: expected.get(i).cloned().flatten(),
+                elapsed,
+            }
+        })
+        .collect();
+
+    DayResult {
+        day: day.day,
+        name: day.name,
+        source: day.source,
+        profile: profile::DEFAULT.to_string(),
+        input_hash: utils::hash(input),
+        parts,
+    }
+}
+
+pub fn run(day: &Day, config: &Config) -> io::Result<DayResult> {
+    Ok(run_input(day, config, &Input::read(&input_path(day.day))?, profile::DEFAULT))
+}
+
+/// The configured time budgets of both parts.
+pub fn timeouts(day: &Day, config: &Config) -> [Option<Duration>; 2] {
+    [config.timeout(day.day, 1), config.timeout(day.day, 2)]
+}
+
+/// Runs a day on an input that was already loaded, e.g. memory-mapped, and
+/// checks it against the answers of the profile the input is from.
+pub fn run_input(day: &Day, config: &Config, input: &str, profile: &str) -> DayResult {
+    let timeouts = timeouts(day, config);
+    let mut result = match config.params(day.day, day.params) {
+        // The known answers are for the default parameters only
+        Ok(params) if params.is_overridden() => run_day(day, input, &params, &[], &timeouts),
+        Ok(params) => run_day(day, input, &params, &read_answers(&profile::answers_path(profile, day.day)), &timeouts),
+        Err(e) => failed_day(day, input, e),
+    };
+
+    result.profile = profile.to_string();
+    result
+}
+
+fn failed_day(day: &Day, input: &str, error: Error) -> DayResult {
+    let failed = |part| PartResult {
+        part,
+        answer: Err(error.clone()),
+        expected: None,
+        elapsed: Duration::default(),
+    };
+
+    DayResult {
+        day: day.day,
+        name: day.name,
+        source: day.source,
+        profile: profile::DEFAULT.to_string(),
+        input_hash: utils::hash(input),
+        parts: vec![failed(1), failed(2)],
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::error::ParseError;
+    use aoc_core::registry::DEFAULT;
+
+    fn count_lines(input: &str, _params: &Params) -> Result<Answer, Error> {
+        Ok(input.lines().count().into())
+    }
+
+    fn fail(input: &str, _params: &Params) -> Result<Answer, Error> {
+        Err(ParseError::new("bad", input).into())
+    }
+
+    #[test]
+    fn test_run_day() {
+        let day = Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &count_lines, part_2: &fail, params: &[] };
+        let result = run_day(&day, "a\nb", &Params::default(), &[Some("2".to_string())], &[]);
+
+        assert_eq!(result.day, 1);
+        assert_eq!(result.parts[0].answer, Ok(Answer::Integer(2)));
+        assert_eq!(result.parts[0].status(), Status::Pass);
+        assert_eq!(result.parts[1].status(), Status::Fail);
+        assert_eq!(
+            result.to_string(),
+            "======== Day 1 ========\nPart 1 = 2\nPart 2 failed: parse error: bad in \"a\\nb\"\n"
+        );
+    }
+
+    fn explode(_input: &str, _params: &Params) -> Result<Answer, Error> {
+        panic!("index out of bounds")
+    }
+
+    #[test]
+    fn test_panic() {
+        let day = Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &explode, part_2: &count_lines, params: &[] };
+        let result = run_day(&day, "a", &Params::default(), &[], &[]);
+
+        assert_eq!(result.parts[0].answer, Err(Error::Panic("index out of bounds".to_string())));
+        assert_eq!(result.parts[1].answer, Ok(Answer::Integer(1)));
+    }
+
+    #[test]
+    fn test_status() {
+        let day = Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &count_lines, part_2: &count_lines, params: &[] };
+        let result = run_day(&day, "a", &Params::default(), &[Some("3".to_string())], &[]);
+
+        assert_eq!(result.parts[0].status(), Status::Fail);
+        assert_eq!(result.parts[1].status(), Status::Unknown);
+        assert!(result.to_string().contains("Part 1 = 1 (expected 3)"));
+    }
+
+    fn spin(_input: &str, _params: &Params) -> Result<Answer, Error> {
+        loop {
+            cancel::check()?;
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        let day = Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &spin, part_2: &count_lines, params: &[] };
+        let budget = Duration::from_millis(10);
+        let result = run_day(&day, "a", &Params::default(), &[], &[Some(budget), Some(budget)]);
+
+        assert_eq!(result.parts[0].answer, Err(Error::Timeout(budget)));
+        assert_eq!(result.parts[1].answer, Ok(Answer::Integer(1)));
+        assert!(result.to_string().contains("Part 1 failed: timed out after 10ms"));
+    }
+}
@@ -0,0 +1,445 @@
+//! A small HTTP API for calling the solvers from other tools.
+//!
+//! - `GET /days` lists the registered days and their parameters.
+//! - `POST /solve` takes `{"day": 1, "part": 2, "input": "..."}`, optionally
+//!   with `"name"` to pick a plugin and `"params"` to override parameters, and
+//!   returns the answer with the time it took.
+//!
+//! Parameters and time budgets from the config apply to every request, with
+//! the request's own parameters on top.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+use aoc_core::cancel::{self, Token};
+use aoc_core::error::Error;
+use aoc_core::params::Config;
+use aoc_core::registry::{Day, DEFAULT};
+use crate::runner;
+
+
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub max_body: usize,
+    /// How long to wait for a solver the config has no budget for. A solver
+    /// that times out is cancelled, but one that doesn't check for that keeps
+    /// running in the background until it's done.
+    pub timeout: Duration,
+    /// How many connections are handled at once, which is also how many
+    /// solvers may be running, counting the ones that timed out but haven't
+    /// stopped yet.
+    pub workers: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_body: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+            workers: thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl ToString) -> Self {
+        Self { status, body: json!({ "error": message.to_string() }) }
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "Internal Server Error",
+        };
+        let body = self.body.to_string();
+
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status, reason, body.len(), body
+        )
+    }
+}
+
+
+/// The solvers that are running, which stays taken until the solver returns.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(running: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| if n < max { Some(n + 1) } else { None })
+            .ok()
+            .map(|_| Slot(running.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+
+struct Server {
+    days: Vec<Day>,
+    config: Config,
+    limits: Limits,
+    running: Arc<AtomicUsize>,
+}
+
+pub fn serve(addr: &str, days: Vec<Day>, config: Config, limits: Limits) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!("Listening on http://{}", listener.local_addr()?);
+
+    let server = Arc::new(Server { days, config, limits, running: Arc::default() });
+    // Connections wait in the accept queue while all the workers are busy
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(0);
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    for _ in 0..limits.workers.max(1) {
+        let (server, receiver) = (server.clone(), receiver.clone());
+
+        thread::spawn(move || loop {
+            let stream = match receiver.lock().unwrap().recv() {
+                Ok(stream) => stream,
+                Err(_) => return,
+            };
+            if let Err(e) = server.handle(stream) {
+                warn!(%e, "connection failed");
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let _ = sender.send(stream);
+            },
+            Err(e) => warn!(%e, "accept failed"),
+        }
+    }
+
+    Ok(())
+}
+
+impl Server {
+    fn handle(&self, mut stream: TcpStream) -> io::Result<()> {
+        // Don't let a slow client hold on to the connection forever
+        stream.set_read_timeout(Some(self.limits.timeout))?;
+
+        let response = match read_request(&mut BufReader::new(&stream), self.limits.max_body) {
+            Ok(request) => {
+                let response = self.route(&request);
+                info!(method = %request.method, path = %request.path, status = response.status, "request");
+                response
+            },
+            Err(response) => response,
+        };
+
+        response.write_to(&mut stream)
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/days") => list(&self.days),
+            ("POST", "/solve") => match serde_json::from_slice(&request.body) {
+                Ok(body) => self.solve(&body),
+                Err(e) => Response::error(400, format!("invalid JSON: {}", e)),
+            },
+            (_, "/days") | (_, "/solve") => Response::error(405, "method not allowed"),
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    fn solve(&self, body: &Value) -> Response {
+        let (number, part, input) = match (body["day"].as_u64(), body["part"].as_u64(), body["input"].as_str()) {
+            (Some(day), Some(part), Some(input)) => (day, part, input.to_string()),
+            _ => return Response::error(400, "expected day, part and input"),
+        };
+        let name = body["name"].as_str().unwrap_or(DEFAULT);
+
+        let day = match self.days.iter().find(|d| u64::from(d.day) == number && d.name == name) {
+            Some(day) => day,
+            None => return Response::error(404, format!("no day {} named {:?}", number, name)),
+        };
+        let (part, solver) = match u8::try_from(part).ok().and_then(|p| Some((p, day.part(p)?))) {
+            Some(found) => found,
+            None => return Response::error(404, format!("no part {}", part)),
+        };
+
+        let overrides: HashMap<String, String> = match &body["params"] {
+            Value::Null => HashMap::new(),
+            Value::Object(params) => params
+                .iter()
+                .map(|(name, value)| (name.clone(), value.as_str().map_or_else(|| value.to_string(), str::to_string)))
+                .collect(),
+            _ => return Response::error(400, "params must be an object"),
+        };
+        let params = match self.config.params_with(day.day, day.params, &overrides) {
+            Ok(params) => params,
+            Err(e) => return Response::error(400, e),
+        };
+
+        let slot = match Slot::take(&self.running, self.limits.workers) {
+            Some(slot) => slot,
+            None => return Response::error(503, "too many solvers running"),
+        };
+        let budget = self.config.timeout(day.day, part).unwrap_or(self.limits.timeout);
+        let (sender, receiver) = mpsc::channel();
+        let token = Token::with_budget(budget);
+        let solver_token = token.clone();
+        thread::spawn(move || {
+            let _slot = slot;
+            let start = Instant::now();
+            let answer = cancel::with_token(solver_token, || runner::solve(solver, &input, &params));
+            let _ = sender.send((answer, start.elapsed()));
+        });
+
+        let result = receiver.recv_timeout(budget);
+        if result.is_err() {
+            token.cancel();
+        }
+
+        match result {
+            Ok((Ok(answer), elapsed)) => Response {
+                status: 200,
+                body: json!({ "day": number, "part": part, "answer": answer.to_string(), "elapsed_ms": elapsed.as_secs_f64() * 1000.0 }),
+            },
+            Ok((Err(e @ Error::Timeout(_)), _)) => Response::error(504, e),
+            Ok((Err(e @ Error::Panic(_)), _)) => Response::error(500, e),
+            Ok((Err(e), _)) => Response::error(422, e),
+            Err(RecvTimeoutError::Timeout) => Response::error(504, Error::Timeout(budget)),
+            Err(RecvTimeoutError::Disconnected) => Response::error(500, "solver stopped without an answer"),
+        }
+    }
+}
+
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let mut header_bytes = 0;
+    let mut read_line = || -> Result<String, Response> {
+        let mut line = String::new();
+        let n = reader
+            .by_ref()
+            .take((MAX_HEADER_BYTES - header_bytes) as u64)
+            .read_line(&mut line)
+            .map_err(|e| Response::error(400, e))?;
+        header_bytes += n;
+
+        match line.strip_suffix('\n') {
+            Some(line) => Ok(line.trim_end_matches('\r').to_string()),
+            None if header_bytes >= MAX_HEADER_BYTES => Err(Response::error(431, "headers too large")),
+            None => Err(Response::error(400, "unexpected end of request")),
+        }
+    };
+
+    let request_line = read_line()?;
+    let mut parts = request_line.split(' ');
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) if !method.is_empty() => (method.to_string(), path.to_string()),
+        _ => return Err(Response::error(400, "invalid request line")),
+    };
+
+    let mut content_length = None;
+    loop {
+        let line = read_line()?;
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse::<usize>().map_err(|_| Response::error(400, "invalid Content-Length"))?);
+            }
+        }
+    }
+
+    let length = match (method.as_str(), content_length) {
+        (_, Some(length)) if length > max_body => {
+            return Err(Response::error(413, format!("body is larger than {} bytes", max_body)))
+        },
+        (_, Some(length)) => length,
+        ("POST", None) => return Err(Response::error(411, "Content-Length is required")),
+        (_, None) => 0,
+    };
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|e| Response::error(400, e))?;
+
+    Ok(Request { method, path, body })
+}
+
+
+fn list(days: &[Day]) -> Response {
+    let days: Vec<Value> = days
+        .iter()
+        .map(|day| {
+            let params: Vec<Value> = day
+                .params
+                .iter()
+                .map(|p| json!({ "name": p.name, "default": p.default, "description": p.description }))
+                .collect();
+
+            json!({ "day": day.day, "name": day.name, "parts": [1, 2], "params": params })
+        })
+        .collect();
+
+    Response { status: 200, body: json!({ "days": days }) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answer::Answer;
+    use aoc_core::params::{Param, Params};
+    use aoc_core::registry::Source;
+
+    const PARAMS: &[Param] = &[Param { name: "factor", default: "1", description: "Multiplies the line count" }];
+
+    fn count_lines(input: &str, params: &Params) -> Result<Answer, Error> {
+        Ok((input.lines().count() * params.get::<usize>("factor")?).into())
+    }
+
+    fn forever(_: &str, _: &Params) -> Result<Answer, Error> {
+        thread::sleep(Duration::from_secs(1));
+        Err(Error::NoSolution)
+    }
+
+    fn explode(_: &str, _: &Params) -> Result<Answer, Error> {
+        panic!("index out of bounds")
+    }
+
+    fn server(config: Config) -> Server {
+        let days = vec![
+            Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &count_lines, part_2: &forever, params: PARAMS },
+            Day { day: 1, name: "other", source: Source::Plugin, part_1: &forever, part_2: &explode, params: &[] },
+        ];
+        let limits = Limits { max_body: 1024, timeout: Duration::from_millis(50), workers: 2 };
+
+        Server { days, config, limits, running: Arc::default() }
+    }
+
+    fn post(server: &Server, body: Value) -> Response {
+        let request = Request { method: "POST".to_string(), path: "/solve".to_string(), body: body.to_string().into_bytes() };
+        server.route(&request)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = read_request(&mut "POST /solve HTTP/1.1\r\nHost: x\r\ncontent-length: 4\r\n\r\nbody".as_bytes(), 10);
+        assert_eq!(request, Ok(Request { method: "POST".to_string(), path: "/solve".to_string(), body: b"body".to_vec() }));
+
+        let status = |text: &str| read_request(&mut text.as_bytes(), 10).map_err(|r| r.status).err();
+        assert_eq!(status("GET /days HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(status("POST /solve HTTP/1.1\r\nContent-Length: 11\r\n\r\n"), Some(413));
+        assert_eq!(status("POST /solve HTTP/1.1\r\n\r\n"), Some(411));
+        assert_eq!(status("POST /solve HTTP/1.1\r\nContent-Length: 4\r\n\r\nab"), Some(400));
+        assert_eq!(status(&format!("GET /days HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_HEADER_BYTES))), Some(431));
+        assert_eq!(status("\r\n"), Some(400));
+    }
+
+    #[test]
+    fn test_list() {
+        let request = Request { method: "GET".to_string(), path: "/days".to_string(), body: vec![] };
+        let response = server(Config::default()).route(&request);
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["days"][0]["params"][0]["name"], "factor");
+        assert_eq!(response.body["days"][1]["name"], "other");
+    }
+
+    #[test]
+    fn test_solve() {
+        let server = server(Config::default());
+        let post = |body| post(&server, body);
+        let response = post(json!({ "day": 1, "part": 1, "input": "a\nb\nc" }));
+        assert_eq!((response.status, &response.body["answer"]), (200, &json!("3")));
+        assert!(response.body["elapsed_ms"].is_f64());
+
+        let response = post(json!({ "day": 1, "part": 1, "input": "a", "params": { "factor": 7 } }));
+        assert_eq!(response.body["answer"], "7");
+
+        assert_eq!(post(json!({ "day": 1, "part": 2, "input": "" })).status, 504);
+        assert_eq!(post(json!({ "day": 1, "part": 1, "input": "", "params": { "other": 1 } })).status, 400);
+        assert_eq!(post(json!({ "day": 1, "part": 1, "input": "", "params": { "factor": "x" } })).status, 422);
+        assert_eq!(post(json!({ "day": 1, "part": 3, "input": "" })).status, 404);
+        assert_eq!(post(json!({ "day": 2, "part": 1, "input": "" })).status, 404);
+        assert_eq!(post(json!({ "day": 1, "name": "missing", "part": 1, "input": "" })).status, 404);
+        assert_eq!(post(json!({ "day": 1 })).status, 400);
+    }
+
+    #[test]
+    fn test_solve_config() {
+        let mut config = Config::default();
+        config.set("day_1.factor=3").unwrap();
+        config.set("day_1.part_2.timeout=0.01").unwrap();
+        let server = server(config);
+
+        assert_eq!(post(&server, json!({ "day": 1, "part": 1, "input": "a" })).body["answer"], "3");
+        assert_eq!(post(&server, json!({ "day": 1, "part": 1, "input": "a", "params": { "factor": 5 } })).body["answer"], "5");
+        assert_eq!(
+            post(&server, json!({ "day": 1, "part": 2, "input": "" })),
+            Response::error(504, "timed out after 10ms")
+        );
+    }
+
+    #[test]
+    fn test_solve_failures() {
+        let server = server(Config::default());
+
+        assert_eq!(
+            post(&server, json!({ "day": 1, "name": "other", "part": 2, "input": "" })),
+            Response::error(500, "panicked: index out of bounds")
+        );
+
+        // Solvers that timed out hold on to their worker until they stop
+        server.running.store(2, Ordering::SeqCst);
+        assert_eq!(post(&server, json!({ "day": 1, "part": 1, "input": "" })).status, 503);
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut out = Vec::new();
+        Response::error(404, "not found").write_to(&mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: 21\r\nConnection: close\r\n\r\n{\"error\":\"not found\"}"
+        );
+    }
+}
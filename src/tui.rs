@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use tracing_subscriber::fmt::MakeWriter;
+use aoc_core::params::Config;
+use aoc_core::registry::Day;
+use crate::runner::{self, DayResult, Status};
+
+
+const MAX_LOG_LINES: usize = 1000;
+
+
+#[derive(Default)]
+struct Lines {
+    complete: Vec<String>,
+    partial: String,
+}
+
+/// Collects the formatted tracing output so it can be shown in the log pane.
+#[derive(Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<Lines>>);
+
+impl LogBuffer {
+    fn tail(&self, n: usize) -> Vec<String> {
+        let lines = &self.0.lock().unwrap().complete;
+        lines[lines.len().saturating_sub(n)..].to_vec()
+    }
+}
+
+impl io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut lines = self.0.lock().unwrap();
+        lines.partial.push_str(&String::from_utf8_lossy(buf));
+
+        while let Some(end) = lines.partial.find('\n') {
+            let line: String = lines.partial.drain(..=end).collect();
+            lines.complete.push(line.trim_end().to_string());
+        }
+
+        let excess = lines.complete.len().saturating_sub(MAX_LOG_LINES);
+        lines.complete.drain(..excess);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+
+struct Running {
+    day: u8,
+    started: Instant,
+    result: Receiver<io::Result<DayResult>>,
+}
+
+pub struct App {
+    days: Vec<Day>,
+    selected: usize,
+    results: HashMap<u8, DayResult>,
+    running: Option<Running>,
+    input: String,
+    message: String,
+    config: Config,
+    logs: LogBuffer,
+}
+
+impl App {
+    pub fn new(days: Vec<Day>, config: Config, logs: LogBuffer) -> Self {
+        let mut app = Self {
+            days,
+            selected: 0,
+            results: HashMap::new(),
+            running: None,
+            input: String::new(),
+            message: String::new(),
+            config,
+            logs,
+        };
+        app.load_input();
+        app
+    }
+
+    fn select(&mut self, selected: usize) {
+        if selected < self.days.len() {
+            self.selected = selected;
+            self.load_input();
+        }
+    }
+
+    fn load_input(&mut self) {
+        self.input = match self.days.get(self.selected) {
+            Some(day) => fs::read_to_string(runner::input_path(day.day)).unwrap_or_else(|e| e.to_string()),
+            None => String::new(),
+        };
+    }
+
+    fn run_selected(&mut self) {
+        let day = match (self.days.get(self.selected), &self.running) {
+            (Some(day), None) => *day,
+            _ => return,
+        };
+        let config = self.config.clone();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || sender.send(runner::run(&day, &config)));
+        self.running = Some(Running { day: day.day, started: Instant::now(), result: receiver });
+    }
+
+    /// Picks up the result of a finished run.
+    fn poll(&mut self) {
+        let running = match &self.running {
+            Some(running) => running,
+            None => return,
+        };
+
+        match running.result.try_recv() {
+            Ok(Ok(result)) => {
+                self.message = format!("Day {} finished in {:?}", result.day, result.elapsed());
+                self.results.insert(result.day, result);
+            },
+            Ok(Err(e)) => self.message = format!("Day {}: {}", running.day, e),
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => self.message = format!("Day {} crashed", running.day),
+        }
+
+        self.running = None;
+    }
+}
+
+
+fn stars(result: Option<&DayResult>) -> String {
+    match result {
+        Some(result) => result
+            .parts
+            .iter()
+            .map(|p| if p.status() == Status::Pass { '*' } else { '.' })
+            .collect(),
+        None => "  ".to_string(),
+    }
+}
+
+fn answer(result: Option<&DayResult>, part: usize) -> String {
+    match result.and_then(|r| r.parts.get(part)) {
+        Some(part) => match &part.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => e.to_string(),
+        },
+        None => String::new(),
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let [main, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [list, side] = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(main);
+    let [input, log] = Layout::vertical([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(side);
+
+    let rows = app.days.iter().map(|day| {
+        let result = app.results.get(&day.day);
+        let time = result.map_or(String::new(), |r| format!("{:.2?}", r.elapsed()));
+
+        Row::new(vec![day.day.to_string(), stars(result), answer(result, 0), answer(result, 1), time])
+    });
+    let table = Table::new(rows, [
+        Constraint::Length(3),
+        Constraint::Length(5),
+        Constraint::Fill(1),
+        Constraint::Fill(1),
+        Constraint::Length(10),
+    ])
+        .header(Row::new(vec!["Day", "Stars", "Part 1", "Part 2", "Time"]).add_modifier(Modifier::BOLD))
+        .row_highlight_style(Style::new().reversed())
+        .block(Block::bordered().title("Days"));
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, list, &mut state);
+
+    let input_title = match app.days.get(app.selected) {
+        Some(day) => format!("{} ({} lines)", runner::input_path(day.day), app.input.lines().count()),
+        None => "Input".to_string(),
+    };
+    frame.render_widget(Paragraph::new(app.input.as_str()).block(Block::bordered().title(input_title)), input);
+
+    let log_lines: Vec<Line> = app.logs.tail(log.height.saturating_sub(2) as usize).into_iter().map(Line::from).collect();
+    frame.render_widget(Paragraph::new(log_lines).block(Block::bordered().title("Log")), log);
+
+    let status_text = match &app.running {
+        Some(running) => format!("Running day {}... {:.1?}", running.day, running.started.elapsed()),
+        None if !app.message.is_empty() => app.message.clone(),
+        None => "Up/Down select  Enter run  q quit".to_string(),
+    };
+    frame.render_widget(Line::from(status_text), status);
+}
+
+
+pub fn run(days: Vec<Day>, config: Config, logs: LogBuffer) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, App::new(days, config, logs));
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    loop {
+        app.poll();
+        terminal.draw(|frame| render(frame, &app))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => app.select(app.selected.saturating_sub(1)),
+                KeyCode::Down | KeyCode::Char('j') => app.select(app.selected + 1),
+                KeyCode::Enter | KeyCode::Char('r') => app.run_selected(),
+                _ => {},
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use aoc_core::answer::Answer;
+    use aoc_core::error::Error;
+    use aoc_core::params::Params;
+    use aoc_core::registry::{Source, DEFAULT};
+
+    fn answer_42(_: &str, _: &Params) -> Result<Answer, Error> {
+        Ok(42.into())
+    }
+
+    fn no_solution(_: &str, _: &Params) -> Result<Answer, Error> {
+        Err(Error::NoSolution)
+    }
+
+    fn app() -> App {
+        let days = vec![
+            Day { day: 1, name: DEFAULT, source: Source::Builtin, part_1: &answer_42, part_2: &no_solution, params: &[] },
+            Day { day: 2, name: DEFAULT, source: Source::Builtin, part_1: &answer_42, part_2: &answer_42, params: &[] },
+        ];
+        let mut app = App::new(days, Config::default(), LogBuffer::default());
+        app.input = "1721\n979\n366".to_string();
+        app
+    }
+
+    fn draw(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(100, 16)).unwrap();
+        terminal.draw(|frame| render(frame, app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_days() {
+        let mut app = app();
+        let day = app.days[0];
+        app.results.insert(1, runner::run_day(&day, "", &Params::default(), &[Some("42".to_string())], &[]));
+        writeln!(app.logs.clone(), "INFO part{{day=1 part=1}}: solved").unwrap();
+
+        let screen = draw(&app);
+
+        assert!(screen[1].contains("Day"));
+        assert!(screen[1].contains("Part 1"));
+        assert!(screen[2].contains("*."));
+        assert!(screen[2].contains("42"));
+        assert!(screen[2].contains("no solution"));
+        assert!(screen[3].trim_start_matches('│').trim_start().starts_with('2'));
+        assert!(screen.iter().any(|l| l.contains("data/day_1.txt (3 lines)")));
+        assert!(screen.iter().any(|l| l.contains("979")));
+        assert!(screen.iter().any(|l| l.contains("solved")));
+        assert!(screen[15].contains("Enter run"));
+    }
+
+    #[test]
+    fn test_run_selected() {
+        let mut app = app();
+        app.select(1);
+        app.run_selected();
+
+        assert!(draw(&app)[15].contains("Running day 2"));
+
+        while app.running.is_some() {
+            app.poll();
+        }
+
+        assert!(app.message.starts_with("Day 2 finished"));
+        assert_eq!(app.results[&2].parts[1].answer, Ok(Answer::Integer(42)));
+    }
+
+    #[test]
+    fn test_log_buffer() {
+        let mut logs = LogBuffer::default();
+        for i in 0..MAX_LOG_LINES + 5 {
+            writeln!(logs, "line {}", i).unwrap();
+        }
+
+        assert_eq!(logs.tail(2), vec![format!("line {}", MAX_LOG_LINES + 3), format!("line {}", MAX_LOG_LINES + 4)]);
+        assert_eq!(logs.tail(usize::MAX).len(), MAX_LOG_LINES);
+    }
+}